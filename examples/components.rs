@@ -0,0 +1,32 @@
+//! Lists every audio unit component installed on the system, then instantiates the first effect.
+
+extern crate coreaudio;
+
+use coreaudio::audio_unit::{AudioComponent, AudioUnit, ComponentDescription};
+
+fn main() {
+    run().unwrap()
+}
+
+fn run() -> Result<(), coreaudio::Error> {
+    let any = ComponentDescription::any();
+    println!("Found {} components:", AudioComponent::count(&any));
+    for component in AudioComponent::find(&any) {
+        let ty = try!(component.ty());
+        let manufacturer = try!(component.manufacturer());
+        let name = try!(component.name());
+        let version = try!(component.version());
+        println!("  {} {} {} (version {:#x})", ty, manufacturer, name, version);
+    }
+
+    let effects = ComponentDescription {
+        component_type: Some(coreaudio::FourCC::from_bytes(b"aufx")),
+        ..ComponentDescription::any()
+    };
+    if let Some(component) = AudioComponent::find_first(&effects) {
+        let _audio_unit = try!(AudioUnit::from_component(component));
+        println!("Instantiated {}", try!(component.name()));
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 extern crate coreaudio;
 
-use coreaudio::audio_unit::{AudioUnit, IOType, SampleFormat};
+use coreaudio::audio_unit::{AudioUnit, Element, IOType, SampleFormat};
 use coreaudio::audio_unit::render_callback::{self, data};
 use std::f64::consts::PI;
 
@@ -41,20 +41,17 @@ fn main() -> Result<(), coreaudio::Error> {
     // Construct an Output audio unit that delivers audio to the default output device.
     let mut audio_unit = AudioUnit::new(IOType::DefaultOutput)?;
 
-    let stream_format = audio_unit.output_stream_format()?;
+    let stream_format = audio_unit.input_stream_format()?;
     println!("{:#?}", &stream_format);
 
     // For this example, our sine wave expects `f32` data.
     assert!(SampleFormat::F32 == stream_format.sample_format);
 
-    type Args = render_callback::Args<data::NonInterleaved<f32>>;
-    audio_unit.set_render_callback(move |args| {
-        let Args { num_frames, mut data, .. } = args;
-        for i in 0..num_frames {
-            let sample = samples.next().unwrap();
-            for channel in data.channels_mut() {
-                channel[i] = sample;
-            }
+    type Args<'a> = render_callback::Args<'a, data::NonInterleaved<f32>>;
+    audio_unit.set_render_callback(Element::Output, move |args| {
+        let Args { data, .. } = args;
+        for mut frame in data.frames_mut() {
+            frame.fill(samples.next().unwrap());
         }
         Ok(())
     })?;
@@ -62,5 +59,5 @@ fn main() -> Result<(), coreaudio::Error> {
 
     std::thread::sleep(std::time::Duration::from_millis(3000));
 
-    Ok(())
+    audio_unit.close()
 }
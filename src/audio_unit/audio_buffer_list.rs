@@ -0,0 +1,176 @@
+//! An owned `AudioBufferList` along with the storage for each of its buffers.
+//!
+//! The `sys::AudioBufferList` is a variable-length C struct whose `mBuffers` field is declared as
+//! a one-element array, while the list may actually hold any number of buffers. The
+//! [**AudioBufferListBuf**](./struct.AudioBufferListBuf) allocates the list with the correct
+//! layout for the requested number of buffers and owns the data that each buffer points to.
+
+use std::cmp;
+use std::mem;
+use std::slice;
+use super::stream_format::{Layout, StreamFormat};
+use sys;
+
+
+/// An owned, correctly laid out `sys::AudioBufferList` with a variable number of buffers.
+///
+/// Each buffer points to its own allocation, aligned to `ALIGN` bytes. All allocation happens
+/// within `new`, `from_stream_format` and `reserve`, so that none is required when the list is
+/// used on the real-time render thread. There, `set_data_byte_size` may be used to adjust the
+/// size of each buffer within its capacity.
+pub struct AudioBufferListBuf {
+    // The storage for the list header and its buffers. `sys::AudioBufferList` is used as the
+    // element type so that the storage is correctly aligned for it.
+    list: Vec<sys::AudioBufferList>,
+    // The storage for each buffer's data.
+    data: Vec<Vec<Block>>,
+    // The size of each buffer's storage in bytes.
+    capacity: usize,
+}
+
+/// The alignment of each buffer's data in bytes.
+pub const ALIGN: usize = 16;
+
+// A unit of aligned buffer storage.
+#[derive(Copy, Clone)]
+#[repr(C, align(16))]
+struct Block([u8; ALIGN]);
+
+
+impl AudioBufferListBuf {
+
+    /// Allocate a list of `num_buffers` buffers, each with `channels_per_buffer` interleaved
+    /// channels and room for `bytes_per_buffer` bytes of zeroed data.
+    pub fn new(num_buffers: usize, channels_per_buffer: u32, bytes_per_buffer: usize) -> Self {
+        let size = buffers_offset() + num_buffers * mem::size_of::<sys::AudioBuffer>();
+        let list_size = mem::size_of::<sys::AudioBufferList>();
+        let len = (size + list_size - 1) / list_size;
+        let mut buffer_list = AudioBufferListBuf {
+            list: (0..len).map(|_| unsafe { mem::zeroed() }).collect(),
+            data: (0..num_buffers).map(|_| Vec::new()).collect(),
+            capacity: 0,
+        };
+        buffer_list.list[0].mNumberBuffers = num_buffers as u32;
+        for buffer in buffer_list.buffers_mut() {
+            buffer.mNumberChannels = channels_per_buffer;
+        }
+        buffer_list.reserve(bytes_per_buffer);
+        buffer_list
+    }
+
+    /// Allocate a list with room for `num_frames` frames of audio in the given format.
+    ///
+    /// Interleaved formats use a single buffer holding every channel, while non-interleaved
+    /// formats use a buffer per channel.
+    pub fn from_stream_format(format: &StreamFormat, num_frames: usize) -> Self {
+        let bytes = num_frames * format.bytes_per_frame() as usize;
+        let channels = format.channels_per_frame;
+        match format.layout {
+            Layout::Interleaved => AudioBufferListBuf::new(1, channels, bytes),
+            Layout::NonInterleaved => AudioBufferListBuf::new(channels as usize, 1, bytes),
+        }
+    }
+
+    /// The number of buffers within the list.
+    pub fn num_buffers(&self) -> usize {
+        self.data.len()
+    }
+
+    /// The size of the storage of each buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Ensure that each buffer has storage for at least `bytes_per_buffer` bytes and set the
+    /// size of each buffer to `bytes_per_buffer`.
+    ///
+    /// This reallocates the storage if necessary, so it should not be called on the real-time
+    /// render thread. The existing data is preserved.
+    pub fn reserve(&mut self, bytes_per_buffer: usize) {
+        if bytes_per_buffer > self.capacity {
+            let blocks = (bytes_per_buffer + ALIGN - 1) / ALIGN;
+            for data in &mut self.data {
+                data.resize(blocks, Block([0; ALIGN]));
+            }
+            self.capacity = blocks * ALIGN;
+            // The buffers may have moved, so update the pointers within the list.
+            let pointers: Vec<_> = self.data.iter_mut().map(|data| data.as_mut_ptr()).collect();
+            for (buffer, ptr) in self.buffers_mut().iter_mut().zip(pointers) {
+                buffer.mData = ptr as *mut _;
+            }
+        }
+        self.set_data_byte_size(bytes_per_buffer);
+    }
+
+    /// Set the `mDataByteSize` of each buffer, e.g. to match the number of frames requested by
+    /// a render callback.
+    ///
+    /// The size is clamped to the `capacity`. This never allocates, so it is safe to call on the
+    /// real-time render thread.
+    pub fn set_data_byte_size(&mut self, bytes_per_buffer: usize) {
+        let size = cmp::min(bytes_per_buffer, self.capacity) as u32;
+        for buffer in self.buffers_mut() {
+            buffer.mDataByteSize = size;
+        }
+    }
+
+    /// The `AudioBuffer`s within the list.
+    pub fn buffers(&self) -> &[sys::AudioBuffer] {
+        let len = self.num_buffers();
+        unsafe {
+            let ptr = (self.list.as_ptr() as *const u8).add(buffers_offset());
+            slice::from_raw_parts(ptr as *const sys::AudioBuffer, len)
+        }
+    }
+
+    /// The data of the buffer at the given index, sized by its `mDataByteSize`.
+    pub fn buffer(&self, index: usize) -> Option<&[u8]> {
+        self.buffers().get(index).map(|buffer| {
+            let size = cmp::min(buffer.mDataByteSize as usize, self.capacity);
+            unsafe { slice::from_raw_parts(self.data[index].as_ptr() as *const u8, size) }
+        })
+    }
+
+    /// The mutable data of the buffer at the given index, sized by its `mDataByteSize`.
+    pub fn buffer_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        let capacity = self.capacity;
+        let size = match self.buffers().get(index) {
+            Some(buffer) => cmp::min(buffer.mDataByteSize as usize, capacity),
+            None => return None,
+        };
+        let ptr = self.data[index].as_mut_ptr() as *mut u8;
+        Some(unsafe { slice::from_raw_parts_mut(ptr, size) })
+    }
+
+    /// A pointer to the list for passing to the Core Audio API.
+    pub fn as_ptr(&self) -> *const sys::AudioBufferList {
+        self.list.as_ptr()
+    }
+
+    /// A mutable pointer to the list for passing to the Core Audio API, e.g. `AudioUnitRender`.
+    ///
+    /// Core Audio may change the `mDataByteSize` of each buffer, but must not change the number
+    /// of buffers or the pointers to their data.
+    pub fn as_mut_ptr(&mut self) -> *mut sys::AudioBufferList {
+        self.list.as_mut_ptr()
+    }
+
+    fn buffers_mut(&mut self) -> &mut [sys::AudioBuffer] {
+        let len = self.num_buffers();
+        unsafe {
+            let ptr = (self.list.as_mut_ptr() as *mut u8).add(buffers_offset());
+            slice::from_raw_parts_mut(ptr as *mut sys::AudioBuffer, len)
+        }
+    }
+
+}
+
+
+// The offset of the `mBuffers` array within the list, i.e. the offset of its single declared
+// element.
+fn buffers_offset() -> usize {
+    mem::size_of::<sys::AudioBufferList>() - mem::size_of::<sys::AudioBuffer>()
+}
+
+
+unsafe impl Send for AudioBufferListBuf {}
@@ -0,0 +1,253 @@
+//! Discovery of the audio unit components installed on the system.
+//!
+//! An **AudioComponent** is a reference to an installed audio unit that has not yet been
+//! instantiated. Components are found by iterating over every component that matches a
+//! (possibly partial) [**ComponentDescription**](./struct.ComponentDescription), and may then be
+//! instantiated with [**AudioUnit::from_component**](../struct.AudioUnit#method.from_component).
+//!
+//! Original documentation [here](https://developer.apple.com/documentation/audiotoolbox/audio_component_services).
+
+use error::Error;
+use four_cc::FourCC;
+use std::mem;
+use std::os::raw::c_uint;
+use std::ptr;
+use super::cf;
+use super::Type;
+use sys;
+
+
+/// The manufacturer code used by all audio units provided by Apple.
+pub const MANUFACTURER_APPLE: FourCC = FourCC::from_bytes(b"appl");
+
+
+bitflags! {
+    /// Flags describing the capabilities of an **AudioComponent**.
+    pub struct AudioComponentFlags: u32 {
+        /// The component is not found by searches unless explicitly asked for via the mask.
+        const UNSEARCHABLE = 1;
+        /// The component can be loaded within a sandboxed process.
+        const SANDBOX_SAFE = 2;
+        /// The component is a version 3 audio unit, implemented as an app extension.
+        const IS_V3_AUDIO_UNIT = 4;
+        /// The component must be instantiated asynchronously.
+        const REQUIRES_ASYNC_INSTANTIATION = 8;
+        /// The component can be loaded into the host's process.
+        const CAN_LOAD_IN_PROCESS = 16;
+    }
+}
+
+
+/// A description of the audio unit component(s) to search for.
+///
+/// Each of the `component_type`, `component_subtype` and `manufacturer` fields act as a wildcard
+/// when `None`, matching any component.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComponentDescription {
+    /// The component type, e.g. `'aufx'` for effects.
+    pub component_type: Option<FourCC>,
+    /// The component subtype, e.g. `'lmtr'` for the peak limiter.
+    pub component_subtype: Option<FourCC>,
+    /// The manufacturer of the component, e.g. `MANUFACTURER_APPLE`.
+    pub manufacturer: Option<FourCC>,
+    /// The flags that a component must have set, for each flag set within `flags_mask`.
+    pub flags: AudioComponentFlags,
+    /// The flags that are to be compared against `flags`.
+    pub flags_mask: AudioComponentFlags,
+}
+
+/// A reference to an audio unit component that is installed on the system.
+///
+/// Components are owned by the system, so an **AudioComponent** may be freely copied.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AudioComponent {
+    component: sys::AudioComponent,
+}
+
+/// An iterator yielding every **AudioComponent** that matches a **ComponentDescription**.
+///
+/// Produced by [**AudioComponent::find**](./struct.AudioComponent#method.find).
+pub struct Components {
+    desc: sys::AudioComponentDescription,
+    current: sys::AudioComponent,
+    finished: bool,
+}
+
+
+impl ComponentDescription {
+
+    /// A description that matches every component.
+    pub fn any() -> Self {
+        ComponentDescription {
+            component_type: None,
+            component_subtype: None,
+            manufacturer: None,
+            flags: AudioComponentFlags::empty(),
+            flags_mask: AudioComponentFlags::empty(),
+        }
+    }
+
+    /// A description that matches every component of the given type and subtype.
+    ///
+    /// A subtype of `FourCC(0)` (e.g. `MusicEffectType::Unknown(FourCC(0))`) matches every
+    /// subtype.
+    pub fn from_type<T>(ty: T) -> Self
+        where T: Into<Type>,
+    {
+        let ty = ty.into();
+        let subtype = match ty.subtype_four_cc() {
+            Some(FourCC(0)) | None => None,
+            subtype => subtype,
+        };
+        ComponentDescription {
+            component_type: Some(ty.four_cc()),
+            component_subtype: subtype,
+            ..ComponentDescription::any()
+        }
+    }
+
+    /// Only match components by the given manufacturer.
+    pub fn manufacturer(self, manufacturer: FourCC) -> Self {
+        ComponentDescription { manufacturer: Some(manufacturer), ..self }
+    }
+
+    /// Convert from the `sys::AudioComponentDescription`, where `0` represents a wildcard.
+    pub fn from_sys(desc: sys::AudioComponentDescription) -> Self {
+        let wildcard = |code| if code == 0 { None } else { Some(FourCC(code)) };
+        ComponentDescription {
+            component_type: wildcard(desc.componentType),
+            component_subtype: wildcard(desc.componentSubType),
+            manufacturer: wildcard(desc.componentManufacturer),
+            flags: retain_bits!(AudioComponentFlags, desc.componentFlags),
+            flags_mask: retain_bits!(AudioComponentFlags, desc.componentFlagsMask),
+        }
+    }
+
+    /// Convert to the `sys::AudioComponentDescription` expected by the Audio Component API.
+    pub fn to_sys(&self) -> sys::AudioComponentDescription {
+        let code = |code: Option<FourCC>| code.map(|c| c.to_u32()).unwrap_or(0) as c_uint;
+        sys::AudioComponentDescription {
+            componentType: code(self.component_type),
+            componentSubType: code(self.component_subtype),
+            componentManufacturer: code(self.manufacturer),
+            componentFlags: self.flags.bits(),
+            componentFlagsMask: self.flags_mask.bits(),
+        }
+    }
+
+    /// The `Type` described by the `component_type` and `component_subtype`, if neither are
+    /// wildcards.
+    pub fn ty(&self) -> Option<Type> {
+        match (self.component_type, self.component_subtype) {
+            (Some(ty), Some(subtype)) => Some(Type::from_u32(ty.to_u32(), subtype.to_u32())),
+            _ => None,
+        }
+    }
+
+}
+
+
+impl AudioComponent {
+
+    /// Iterate over every component that matches the given description.
+    ///
+    /// Components are yielded in the system-defined order, so the first component yielded is
+    /// the one that `AudioUnit::new` would use.
+    pub fn find(desc: &ComponentDescription) -> Components {
+        Components {
+            desc: desc.to_sys(),
+            current: ptr::null_mut(),
+            finished: false,
+        }
+    }
+
+    /// The number of components that match the given description.
+    pub fn count(desc: &ComponentDescription) -> usize {
+        let desc = desc.to_sys();
+        unsafe { sys::AudioComponentCount(&desc as *const _) as usize }
+    }
+
+    /// The first component that matches the given description, if any.
+    pub fn find_first(desc: &ComponentDescription) -> Option<AudioComponent> {
+        AudioComponent::find(desc).next()
+    }
+
+    /// The name of the component, usually in the form `"Manufacturer: Name"`.
+    pub fn name(&self) -> Result<String, Error> {
+        unsafe {
+            let mut name: sys::CFStringRef = ptr::null();
+            try!(Error::from_os_status(sys::AudioComponentCopyName(self.component, &mut name)));
+            cf::to_string_and_release(name).ok_or(Error::Unspecified)
+        }
+    }
+
+    /// The version of the component, in the form `0xMMMMmmDD` (major, minor, dot release).
+    pub fn version(&self) -> Result<u32, Error> {
+        unsafe {
+            let mut version = 0;
+            try!(Error::from_os_status(
+                sys::AudioComponentGetVersion(self.component, &mut version)
+            ));
+            Ok(version)
+        }
+    }
+
+    /// The full description of the component, including its type, subtype and manufacturer.
+    pub fn description(&self) -> Result<ComponentDescription, Error> {
+        unsafe {
+            let mut desc: sys::AudioComponentDescription = mem::zeroed();
+            try!(Error::from_os_status(
+                sys::AudioComponentGetDescription(self.component, &mut desc)
+            ));
+            Ok(ComponentDescription::from_sys(desc))
+        }
+    }
+
+    /// The `Type` of the component.
+    pub fn ty(&self) -> Result<Type, Error> {
+        let desc = try!(self.description());
+        Ok(desc.ty().unwrap_or(Type::Unknown(
+            desc.component_type.unwrap_or_default(),
+            desc.component_subtype.unwrap_or_default(),
+        )))
+    }
+
+    /// The manufacturer of the component.
+    pub fn manufacturer(&self) -> Result<FourCC, Error> {
+        let desc = try!(self.description());
+        Ok(desc.manufacturer.unwrap_or_default())
+    }
+
+    /// The flags describing the capabilities of the component.
+    pub fn flags(&self) -> Result<AudioComponentFlags, Error> {
+        let desc = try!(self.description());
+        Ok(desc.flags)
+    }
+
+    /// The underlying `sys::AudioComponent`.
+    pub fn as_sys(&self) -> sys::AudioComponent {
+        self.component
+    }
+
+}
+
+
+unsafe impl Send for AudioComponent {}
+
+
+impl Iterator for Components {
+    type Item = AudioComponent;
+    fn next(&mut self) -> Option<AudioComponent> {
+        if self.finished {
+            return None;
+        }
+        let next = unsafe { sys::AudioComponentFindNext(self.current, &self.desc as *const _) };
+        // Searching from a null component would start again from the beginning.
+        if next.is_null() {
+            self.finished = true;
+            return None;
+        }
+        self.current = next;
+        Some(AudioComponent { component: next })
+    }
+}
@@ -3,8 +3,58 @@
 //! See the Core Audio Data Types Reference
 //! [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Reference/CoreAudioDataTypesRef/#//apple_ref/doc/constant_group/Audio_Data_Format_Identifiers) for more info.
 
+use error::audio_format::Error;
+use four_cc::FourCC;
+use std::fmt;
 use std::os::raw::c_uint;
 
+// The `AudioFormatID` of each format.
+mod id {
+    use four_cc::FourCC;
+    pub const LINEAR_PCM:             u32 = FourCC::from_bytes(b"lpcm").0;
+    pub const AC3:                    u32 = FourCC::from_bytes(b"ac-3").0;
+    pub const F60958_AC3:             u32 = FourCC::from_bytes(b"cac3").0;
+    pub const APPLE_IMA4:             u32 = FourCC::from_bytes(b"ima4").0;
+    pub const MPEG4_AAC:              u32 = FourCC::from_bytes(b"aac ").0;
+    pub const MPEG4_CELP:             u32 = FourCC::from_bytes(b"celp").0;
+    pub const MPEG4_HVXC:             u32 = FourCC::from_bytes(b"hvxc").0;
+    pub const MPEG4_TWIN_VQ:          u32 = FourCC::from_bytes(b"twvq").0;
+    pub const MACE3:                  u32 = FourCC::from_bytes(b"MAC3").0;
+    pub const MACE6:                  u32 = FourCC::from_bytes(b"MAC6").0;
+    pub const U_LAW:                  u32 = FourCC::from_bytes(b"ulaw").0;
+    pub const A_LAW:                  u32 = FourCC::from_bytes(b"alaw").0;
+    pub const QDESIGN:                u32 = FourCC::from_bytes(b"QDMC").0;
+    pub const QDESIGN2:               u32 = FourCC::from_bytes(b"QDM2").0;
+    pub const QUALCOMM:               u32 = FourCC::from_bytes(b"Qclp").0;
+    pub const MPEG_LAYER1:            u32 = FourCC::from_bytes(b".mp1").0;
+    pub const MPEG_LAYER2:            u32 = FourCC::from_bytes(b".mp2").0;
+    pub const MPEG_LAYER3:            u32 = FourCC::from_bytes(b".mp3").0;
+    pub const TIME_CODE:              u32 = FourCC::from_bytes(b"time").0;
+    pub const MIDI_STREAM:            u32 = FourCC::from_bytes(b"midi").0;
+    pub const PARAMETER_VALUE_STREAM: u32 = FourCC::from_bytes(b"apvs").0;
+    pub const APPLE_LOSSLESS:         u32 = FourCC::from_bytes(b"alac").0;
+    pub const MPEG4_AAC_HE:           u32 = FourCC::from_bytes(b"aach").0;
+    pub const MPEG4_AAC_LD:           u32 = FourCC::from_bytes(b"aacl").0;
+    pub const MPEG4_AAC_ELD:          u32 = FourCC::from_bytes(b"aace").0;
+    pub const MPEG4_AAC_ELD_SBR:      u32 = FourCC::from_bytes(b"aacf").0;
+    pub const MPEG4_AAC_ELD_V2:       u32 = FourCC::from_bytes(b"aacg").0;
+    pub const MPEG4_AAC_HE_V2:        u32 = FourCC::from_bytes(b"aacp").0;
+    pub const MPEG4_AAC_SPATIAL:      u32 = FourCC::from_bytes(b"aacs").0;
+    pub const AMR:                    u32 = FourCC::from_bytes(b"samr").0;
+    pub const AMR_WB:                 u32 = FourCC::from_bytes(b"sawb").0;
+    pub const AUDIBLE:                u32 = FourCC::from_bytes(b"AUDB").0;
+    pub const ILBC:                   u32 = FourCC::from_bytes(b"ilbc").0;
+    pub const DVI_INTEL_IMA:          u32 = FourCC::from_bytes(b"ms\x00\x11").0;
+    pub const MICROSOFT_GSM:          u32 = FourCC::from_bytes(b"ms\x001").0;
+    pub const AES3:                   u32 = FourCC::from_bytes(b"aes3").0;
+    pub const ENHANCED_AC3:           u32 = FourCC::from_bytes(b"ec-3").0;
+    pub const FLAC:                   u32 = FourCC::from_bytes(b"flac").0;
+    pub const OPUS:                   u32 = FourCC::from_bytes(b"opus").0;
+    pub const LATM_IN_LOAS:           u32 = FourCC::from_bytes(b"loas").0;
+    pub const MPEGD_USAC:             u32 = FourCC::from_bytes(b"usac").0;
+    pub const APAC:                   u32 = FourCC::from_bytes(b"apac").0;
+}
+
 /// A type-safe representation of both the `AudioFormatId` and their associated flags.
 #[derive(Copy, Clone, Debug)]
 #[allow(non_camel_case_types)]
@@ -12,80 +62,80 @@ pub enum AudioFormat {
     /// Linear PCM; a non-compressed audio data format with one frame per packet.
     ///
     /// **Available** in OS X v10.0 and later.
-    LinearPCM(LinearPcmFlags),     // = 1819304813,
+    LinearPCM(LinearPcmFlags),     // = 'lpcm',
     /// An AC-3 codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    AC3,                                  // = 1633889587,
+    AC3,                                  // = 'ac-3',
     /// AC-3 codec that provides data packaged for transport over an IEC 60958 compliant digital
     /// audio interface.
     ///
     /// **Available** in OS X v10.2 and later.
-    F60958AC3(StandardFlags),      // = 1667326771,
+    F60958AC3(StandardFlags),      // = 'cac3',
     /// Apple's implementation of the IMA 4:1 ADPCM codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    AppleIMA4,                            // = 1768775988,
+    AppleIMA4,                            // = 'ima4',
     /// MPEG-4 AAC codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    MPEG4AAC(Mpeg4ObjectId),      // = 1633772320,
+    MPEG4AAC(Mpeg4ObjectId),      // = 'aac ',
     /// MPEG-4 CELP codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    MPEG4CELP(Mpeg4ObjectId),     // = 1667591280,
+    MPEG4CELP(Mpeg4ObjectId),     // = 'celp',
     /// MPEG-4 HVXC codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    MPEG4HVXC(Mpeg4ObjectId),     // = 1752594531,
+    MPEG4HVXC(Mpeg4ObjectId),     // = 'hvxc',
     /// MPEG-4 TwinVQ codec.
     ///
     /// **Available** in OS X v10.2 and later.
-    MPEG4TwinVQ(Mpeg4ObjectId),   // = 1953986161,
+    MPEG4TwinVQ(Mpeg4ObjectId),   // = 'twvq',
     /// MACE 3:1.
     ///
     /// **Available** in OS X v10.3 and later.
-    MACE3,                                // = 1296122675,
+    MACE3,                                // = 'MAC3',
     /// MACE 6:1.
     ///
     /// **Available** in OS X v10.3 and later.
-    MACE6,                                // = 1296122678,
+    MACE6,                                // = 'MAC6',
     /// μLaw 2:1.
     ///
     /// **Available** in OS X v10.3 and later.
-    ULaw,                                 // = 1970037111,
+    ULaw,                                 // = 'ulaw',
     /// aLaw 2:1.
     ///
     /// **Available** in OS X v10.3 and later.
-    ALaw,                                 // = 1634492791,
+    ALaw,                                 // = 'alaw',
     /// QDesign Music.
     ///
     /// **Available** in OS X v10.3 and later.
-    QDesign,                              // = 1363430723,
+    QDesign,                              // = 'QDMC',
     /// QDesign2 Music.
     ///
     /// **Available** in OS X v10.3 and later.
-    QDesign2,                             // = 1363430706,
+    QDesign2,                             // = 'QDM2',
     /// QUALCOMM PureVoice.
     ///
     /// **Available** in OS X v10.3 and later.
-    QUALCOMM,                             // = 1365470320,
+    QUALCOMM,                             // = 'Qclp',
     /// MPEG-1/2, Layer 1 audio.
     ///
     /// **Available** in OS X v10.3 and later.
-    MPEGLayer1,                           // = 778924081,
+    MPEGLayer1,                           // = '.mp1',
     /// MPEG-1/2, Layer 2 audio.
     ///
     /// **Available** in OS X v10.3 and later.
-    MPEGLayer2,                           // = 778924082,
+    MPEGLayer2,                           // = '.mp2',
     /// MPEG-1/2, Layer 3 audio.
     ///
     /// **Available** in OS X v10.3 and later.
-    MPEGLayer3,                           // = 778924083,
+    MPEGLayer3,                           // = '.mp3',
     /// A stream of IOAudioTimeStamp structures.
     ///
     /// **Available** in OS X v10.2 and later.
-    TimeCode(AudioTimeStampFlags), // = 1953066341,
+    TimeCode(AudioTimeStampFlags), // = 'time',
     /// A stream of MIDIPacketList structures where the time stamps in the MIDIPacket structures
     /// are sample offsets in the stream. The `sample_rate` field in the **StreamFormat** structure
     /// is used to describe how time is passed in this kind of stream.
@@ -100,7 +150,7 @@ pub enum AudioFormat {
     ///
     /// TODO: Review whether or not this audio format should indicate some fundamental change
     /// within the **StreamFormat**.
-    MIDIStream,                           // = 1835623529,
+    MIDIStream,                           // = 'midi',
     /// A "side-chain" of f32 data that can be fed or generated by an audio unit and that is used
     /// to send a high density of parameter value control information.
     ///
@@ -111,50 +161,56 @@ pub enum AudioFormat {
     /// The `sample_rate` field in the **StreamFormat** type describes this relationship.
     ///
     /// **Available** in OS X v10.2 and later.
-    ParameterValueStream,                 // = 1634760307,
+    ParameterValueStream,                 // = 'apvs',
     /// Apple Lossless format.
     ///
     /// **Available** in OS X v10.3 and later.
-    AppleLossless(AppleLosslessFlags),    // = 1634492771,
+    AppleLossless(AppleLosslessFlags),    // = 'alac',
     /// MPEG-4 High Efficiency AAC audio object.
     ///
     /// **Available** in OS X v10.5 and later.
-    MPEG4AAC_HE,                          // = 1633772392,
+    MPEG4AAC_HE,                          // = 'aach',
     /// MPEG-4 AAC Low Delay audio object.
     ///
     /// **Available** in OS X v10.5 and later.
-    MPEG4AAC_LD,                          // = 1633772396,
+    MPEG4AAC_LD,                          // = 'aacl',
     /// MPEG-4 AAC Enhanced Low Delay audio object.
     ///
     /// **Available** in OS X v10.7 and later.
-    MPEG4AAC_ELD,                         // = 1633772389,
+    MPEG4AAC_ELD,                         // = 'aace',
     /// MPEG-4 AAC Enhanced Low Delay audio object with SBR (spectral band replication) extension
     /// layer.
     ///
     /// **Available** in OS X v10.7 and later.
-    MPEG4AAC_ELD_SBR,                     // = 1633772390,
-    MPEG4AAC_ELD_V2,                      // = 1633772391,
+    MPEG4AAC_ELD_SBR,                     // = 'aacf',
+    /// MPEG-4 AAC Enhanced Low Delay Version 2 audio object.
+    ///
+    /// **Available** in OS X v10.10 and later.
+    MPEG4AAC_ELD_V2,                      // = 'aacg',
     /// MPEG-4 High Efficiency AAC Version 2 audio object.
     ///
     /// **Available** in OS X v10.5 and later.
-    MPEG4AAC_HE_V2,                       // = 1633772400,
+    MPEG4AAC_HE_V2,                       // = 'aacp',
     /// MPEG-4 Apatial Audio audio object.
     ///
     /// **Available** in OS X v10.5 and later.
-    MPEG4AAC_Spatial,                     // = 1633772403,
+    MPEG4AAC_Spatial,                     // = 'aacs',
     /// The AMR (adaptive Multi-Rate) narrow band speech codec.
     ///
     /// **Available** in OS X v10.5 and later.
-    AMR,                                  // = 1935764850,
-    AMR_WB,                               // = 1935767394,
+    AMR,                                  // = 'samr',
+    /// The AMR (Adaptive Multi-Rate) Wideband speech codec.
+    ///
+    /// **Available** in OS X v10.7 and later.
+    AMR_WB,                               // = 'sawb',
     /// The codec used for Audible, Inc. audio books.
     ///
     /// **Available** in OS X v10.6 and later.
-    Audible,                              // = 1096107074,
+    Audible,                              // = 'AUDB',
     /// The iLBC (internet Low Bitrate Codec) narrow band cpeech codec.
     ///
     /// **Available** in OS X v10.6 and later.
-    iLBC,                                 // = 1768710755,
+    iLBC,                                 // = 'ilbc',
     /// DVI/Intel IMA ADPCM - ACM code 17.
     ///
     /// **Available** in OS X v10.6 and later.
@@ -167,100 +223,186 @@ pub enum AudioFormat {
     ///
     /// Adopted into MXF and MPEG-2 containers and SDTI transport streams with SMPTE specs
     /// 203M-2002 and 331M-2000.
-    AES3,                                 // = 1634038579,
+    AES3,                                 // = 'aes3',
+    /// The Enhanced AC-3 (Dolby Digital Plus) codec.
+    ///
+    /// **Available** in OS X v10.11 and later.
+    EnhancedAC3,                          // = 'ec-3',
+    /// The Free Lossless Audio Codec.
+    ///
+    /// **Available** in OS X v10.13 and later.
+    FLAC(FlacFlags),                      // = 'flac',
+    /// The Opus codec.
+    ///
+    /// **Available** in OS X v10.13 and later.
+    Opus,                                 // = 'opus',
+    /// MPEG-4 AAC audio in a LATM/LOAS transport stream.
+    ///
+    /// **Available** in OS X v10.13 and later.
+    LatmInLoas,                           // = 'loas',
+    /// MPEG-D Unified Speech and Audio Coding.
+    ///
+    /// **Available** in OS X v10.15 and later.
+    MPEGD_USAC,                           // = 'usac',
+    /// The Apple Positional Audio Codec.
+    ///
+    /// **Available** in macOS v14.0 and later.
+    APAC,                                 // = 'apac',
+    /// A format ID that is not known to coreaudio-rs, along with its raw flags.
+    ///
+    /// This allows for any `AudioStreamBasicDescription` to be represented without loss.
+    Other(u32, u32),
 }
 
 
 impl AudioFormat {
 
     /// Convert from the FFI C format and flags to a typesafe Rust enum representation.
-    pub fn from_format_and_flag(format: c_uint, flag: Option<u32>) -> Option<AudioFormat> {
-        match (format, flag) {
-            (1819304813, Some(i)) => Some(AudioFormat::LinearPCM(LinearPcmFlags::from_bits_truncate(i))),
-            (1633889587, _)       => Some(AudioFormat::AC3),
-            (1667326771, Some(i)) => Some(AudioFormat::F60958AC3(StandardFlags::from_bits_truncate(i))),
-            (1768775988, _)       => Some(AudioFormat::AppleIMA4),
-            (1633772320, Some(i)) => Some(AudioFormat::MPEG4AAC(Mpeg4ObjectId::from_u32(i).expect("Unknown Mpeg4ObjectId"))),
-            (1667591280, Some(i)) => Some(AudioFormat::MPEG4CELP(Mpeg4ObjectId::from_u32(i).expect("Unknown Mpeg4ObjectId"))),
-            (1752594531, Some(i)) => Some(AudioFormat::MPEG4HVXC(Mpeg4ObjectId::from_u32(i).expect("Unknown Mpeg4ObjectId"))),
-            (1953986161, Some(i)) => Some(AudioFormat::MPEG4TwinVQ(Mpeg4ObjectId::from_u32(i).expect("Unknown Mpeg4ObjectId"))),
-            (1296122675, _)       => Some(AudioFormat::MACE3),
-            (1296122678, _)       => Some(AudioFormat::MACE6),
-            (1970037111, _)       => Some(AudioFormat::ULaw),
-            (1634492791, _)       => Some(AudioFormat::ALaw),
-            (1363430723, _)       => Some(AudioFormat::QDesign),
-            (1363430706, _)       => Some(AudioFormat::QDesign2),
-            (1365470320, _)       => Some(AudioFormat::QUALCOMM),
-            (778924081, _)        => Some(AudioFormat::MPEGLayer1),
-            (778924082, _)        => Some(AudioFormat::MPEGLayer2),
-            (778924083, _)        => Some(AudioFormat::MPEGLayer3),
-            (1953066341, Some(i)) => Some(AudioFormat::TimeCode(AudioTimeStampFlags::from_bits_truncate(i))),
-            (1835623529, _)       => Some(AudioFormat::MIDIStream),
-            (1634760307, _)       => Some(AudioFormat::ParameterValueStream),
-            (1634492771, Some(i)) => Some(AudioFormat::AppleLossless(AppleLosslessFlags::from_bits_truncate(i))),
-            (1633772392, _)       => Some(AudioFormat::MPEG4AAC_HE),
-            (1633772396, _)       => Some(AudioFormat::MPEG4AAC_LD),
-            (1633772389, _)       => Some(AudioFormat::MPEG4AAC_ELD),
-            (1633772390, _)       => Some(AudioFormat::MPEG4AAC_ELD_SBR),
-            (1633772391, _)       => Some(AudioFormat::MPEG4AAC_ELD_V2),
-            (1633772400, _)       => Some(AudioFormat::MPEG4AAC_HE_V2),
-            (1633772403, _)       => Some(AudioFormat::MPEG4AAC_Spatial),
-            (1935764850, _)       => Some(AudioFormat::AMR),
-            (1935767394, _)       => Some(AudioFormat::AMR_WB),
-            (1096107074, _)       => Some(AudioFormat::Audible),
-            (1768710755, _)       => Some(AudioFormat::iLBC),
-            (1836253201, _)       => Some(AudioFormat::DVIIntelIMA),
-            (1836253233, _)       => Some(AudioFormat::MicrosoftGSM),
-            (1634038579, _)       => Some(AudioFormat::AES3),
-            _                     => None,
-        }
+    ///
+    /// Flag bits that are not known to the associated flags type are retained rather than
+    /// truncated, so that the format may be converted back without loss.
+    ///
+    /// Unknown format IDs, and MPEG-4 formats with an unknown object ID, are represented by
+    /// `AudioFormat::Other`.
+    ///
+    /// Returns `Error::UnknownFormat` if the format requires flags and none were given.
+    pub fn from_format_and_flag(format: c_uint, flag: Option<u32>) -> Result<AudioFormat, Error> {
+        // MPEG-4 formats with an unknown object ID are retained as `Other`.
+        let mpeg4 = |variant: fn(Mpeg4ObjectId) -> AudioFormat, i| {
+            Mpeg4ObjectId::from_u32(i).map(variant).unwrap_or(AudioFormat::Other(format, i))
+        };
+        let format = match (format, flag) {
+            (id::LINEAR_PCM, Some(i))       => {
+                AudioFormat::LinearPCM(retain_bits!(LinearPcmFlags, i))
+            },
+            (id::AC3, _)                    => AudioFormat::AC3,
+            (id::F60958_AC3, Some(i))       => {
+                AudioFormat::F60958AC3(retain_bits!(StandardFlags, i))
+            },
+            (id::APPLE_IMA4, _)             => AudioFormat::AppleIMA4,
+            (id::MPEG4_AAC, Some(i))        => mpeg4(AudioFormat::MPEG4AAC, i),
+            (id::MPEG4_CELP, Some(i))       => mpeg4(AudioFormat::MPEG4CELP, i),
+            (id::MPEG4_HVXC, Some(i))       => mpeg4(AudioFormat::MPEG4HVXC, i),
+            (id::MPEG4_TWIN_VQ, Some(i))    => mpeg4(AudioFormat::MPEG4TwinVQ, i),
+            (id::MACE3, _)                  => AudioFormat::MACE3,
+            (id::MACE6, _)                  => AudioFormat::MACE6,
+            (id::U_LAW, _)                  => AudioFormat::ULaw,
+            (id::A_LAW, _)                  => AudioFormat::ALaw,
+            (id::QDESIGN, _)                => AudioFormat::QDesign,
+            (id::QDESIGN2, _)               => AudioFormat::QDesign2,
+            (id::QUALCOMM, _)               => AudioFormat::QUALCOMM,
+            (id::MPEG_LAYER1, _)            => AudioFormat::MPEGLayer1,
+            (id::MPEG_LAYER2, _)            => AudioFormat::MPEGLayer2,
+            (id::MPEG_LAYER3, _)            => AudioFormat::MPEGLayer3,
+            (id::TIME_CODE, Some(i))        => {
+                AudioFormat::TimeCode(retain_bits!(AudioTimeStampFlags, i))
+            },
+            (id::MIDI_STREAM, _)            => AudioFormat::MIDIStream,
+            (id::PARAMETER_VALUE_STREAM, _) => AudioFormat::ParameterValueStream,
+            (id::APPLE_LOSSLESS, Some(i))   => {
+                AudioFormat::AppleLossless(retain_bits!(AppleLosslessFlags, i))
+            },
+            (id::MPEG4_AAC_HE, _)           => AudioFormat::MPEG4AAC_HE,
+            (id::MPEG4_AAC_LD, _)           => AudioFormat::MPEG4AAC_LD,
+            (id::MPEG4_AAC_ELD, _)          => AudioFormat::MPEG4AAC_ELD,
+            (id::MPEG4_AAC_ELD_SBR, _)      => AudioFormat::MPEG4AAC_ELD_SBR,
+            (id::MPEG4_AAC_ELD_V2, _)       => AudioFormat::MPEG4AAC_ELD_V2,
+            (id::MPEG4_AAC_HE_V2, _)        => AudioFormat::MPEG4AAC_HE_V2,
+            (id::MPEG4_AAC_SPATIAL, _)      => AudioFormat::MPEG4AAC_Spatial,
+            (id::AMR, _)                    => AudioFormat::AMR,
+            (id::AMR_WB, _)                 => AudioFormat::AMR_WB,
+            (id::AUDIBLE, _)                => AudioFormat::Audible,
+            (id::ILBC, _)                   => AudioFormat::iLBC,
+            (id::DVI_INTEL_IMA, _)          => AudioFormat::DVIIntelIMA,
+            (id::MICROSOFT_GSM, _)          => AudioFormat::MicrosoftGSM,
+            (id::AES3, _)                   => AudioFormat::AES3,
+            (id::ENHANCED_AC3, _)           => AudioFormat::EnhancedAC3,
+            (id::FLAC, Some(i))             => AudioFormat::FLAC(retain_bits!(FlacFlags, i)),
+            (id::OPUS, _)                   => AudioFormat::Opus,
+            (id::LATM_IN_LOAS, _)           => AudioFormat::LatmInLoas,
+            (id::MPEGD_USAC, _)             => AudioFormat::MPEGD_USAC,
+            (id::APAC, _)                   => AudioFormat::APAC,
+            (id::LINEAR_PCM, None)          |
+            (id::F60958_AC3, None)          |
+            (id::MPEG4_AAC, None)           |
+            (id::MPEG4_CELP, None)          |
+            (id::MPEG4_HVXC, None)          |
+            (id::MPEG4_TWIN_VQ, None)       |
+            (id::TIME_CODE, None)           |
+            (id::APPLE_LOSSLESS, None)      |
+            (id::FLAC, None)                => return Err(Error::UnknownFormat),
+            (_, flag)                       => AudioFormat::Other(format, flag.unwrap_or(0)),
+        };
+        Ok(format)
+    }
+
+    /// The four-character code identifying the format, e.g. `'lpcm'`.
+    pub fn format_id(&self) -> FourCC {
+        FourCC(self.to_format_and_flag().0)
     }
 
     /// Convert from the Rust enum to the C format and flag.
     pub fn to_format_and_flag(&self) -> (c_uint, Option<u32>) {
         match *self {
-            AudioFormat::LinearPCM(flag)      => (1819304813, Some(flag.bits())),
-            AudioFormat::AC3                  => (1633889587, None),
-            AudioFormat::F60958AC3(flag)      => (1667326771, Some(flag.bits())),
-            AudioFormat::AppleIMA4            => (1768775988, None),
-            AudioFormat::MPEG4AAC(flag)       => (1633772320, Some(flag as u32)),
-            AudioFormat::MPEG4CELP(flag)      => (1667591280, Some(flag as u32)),
-            AudioFormat::MPEG4HVXC(flag)      => (1752594531, Some(flag as u32)),
-            AudioFormat::MPEG4TwinVQ(flag)    => (1953986161, Some(flag as u32)),
-            AudioFormat::MACE3                => (1296122675, None),
-            AudioFormat::MACE6                => (1296122678, None),
-            AudioFormat::ULaw                 => (1970037111, None),
-            AudioFormat::ALaw                 => (1634492791, None),
-            AudioFormat::QDesign              => (1363430723, None),
-            AudioFormat::QDesign2             => (1363430706, None),
-            AudioFormat::QUALCOMM             => (1365470320, None),
-            AudioFormat::MPEGLayer1           => (778924081, None),
-            AudioFormat::MPEGLayer2           => (778924082, None),
-            AudioFormat::MPEGLayer3           => (778924083, None),
-            AudioFormat::TimeCode(flag)       => (1953066341, Some(flag.bits())),
-            AudioFormat::MIDIStream           => (1835623529, None),
-            AudioFormat::ParameterValueStream => (1634760307, None),
-            AudioFormat::AppleLossless(flag)  => (1634492771, Some(flag.bits())),
-            AudioFormat::MPEG4AAC_HE          => (1633772392, None),
-            AudioFormat::MPEG4AAC_LD          => (1633772396, None),
-            AudioFormat::MPEG4AAC_ELD         => (1633772389, None),
-            AudioFormat::MPEG4AAC_ELD_SBR     => (1633772390, None),
-            AudioFormat::MPEG4AAC_ELD_V2      => (1633772391, None),
-            AudioFormat::MPEG4AAC_HE_V2       => (1633772400, None),
-            AudioFormat::MPEG4AAC_Spatial     => (1633772403, None),
-            AudioFormat::AMR                  => (1935764850, None),
-            AudioFormat::AMR_WB               => (1935767394, None),
-            AudioFormat::Audible              => (1096107074, None),
-            AudioFormat::iLBC                 => (1768710755, None),
-            AudioFormat::DVIIntelIMA          => (1836253201, None),
-            AudioFormat::MicrosoftGSM         => (1836253233, None),
-            AudioFormat::AES3                 => (1634038579, None),
+            AudioFormat::LinearPCM(flag)      => (id::LINEAR_PCM, Some(flag.bits())),
+            AudioFormat::AC3                  => (id::AC3, None),
+            AudioFormat::F60958AC3(flag)      => (id::F60958_AC3, Some(flag.bits())),
+            AudioFormat::AppleIMA4            => (id::APPLE_IMA4, None),
+            AudioFormat::MPEG4AAC(flag)       => (id::MPEG4_AAC, Some(flag as u32)),
+            AudioFormat::MPEG4CELP(flag)      => (id::MPEG4_CELP, Some(flag as u32)),
+            AudioFormat::MPEG4HVXC(flag)      => (id::MPEG4_HVXC, Some(flag as u32)),
+            AudioFormat::MPEG4TwinVQ(flag)    => (id::MPEG4_TWIN_VQ, Some(flag as u32)),
+            AudioFormat::MACE3                => (id::MACE3, None),
+            AudioFormat::MACE6                => (id::MACE6, None),
+            AudioFormat::ULaw                 => (id::U_LAW, None),
+            AudioFormat::ALaw                 => (id::A_LAW, None),
+            AudioFormat::QDesign              => (id::QDESIGN, None),
+            AudioFormat::QDesign2             => (id::QDESIGN2, None),
+            AudioFormat::QUALCOMM             => (id::QUALCOMM, None),
+            AudioFormat::MPEGLayer1           => (id::MPEG_LAYER1, None),
+            AudioFormat::MPEGLayer2           => (id::MPEG_LAYER2, None),
+            AudioFormat::MPEGLayer3           => (id::MPEG_LAYER3, None),
+            AudioFormat::TimeCode(flag)       => (id::TIME_CODE, Some(flag.bits())),
+            AudioFormat::MIDIStream           => (id::MIDI_STREAM, None),
+            AudioFormat::ParameterValueStream => (id::PARAMETER_VALUE_STREAM, None),
+            AudioFormat::AppleLossless(flag)  => (id::APPLE_LOSSLESS, Some(flag.bits())),
+            AudioFormat::MPEG4AAC_HE          => (id::MPEG4_AAC_HE, None),
+            AudioFormat::MPEG4AAC_LD          => (id::MPEG4_AAC_LD, None),
+            AudioFormat::MPEG4AAC_ELD         => (id::MPEG4_AAC_ELD, None),
+            AudioFormat::MPEG4AAC_ELD_SBR     => (id::MPEG4_AAC_ELD_SBR, None),
+            AudioFormat::MPEG4AAC_ELD_V2      => (id::MPEG4_AAC_ELD_V2, None),
+            AudioFormat::MPEG4AAC_HE_V2       => (id::MPEG4_AAC_HE_V2, None),
+            AudioFormat::MPEG4AAC_Spatial     => (id::MPEG4_AAC_SPATIAL, None),
+            AudioFormat::AMR                  => (id::AMR, None),
+            AudioFormat::AMR_WB               => (id::AMR_WB, None),
+            AudioFormat::Audible              => (id::AUDIBLE, None),
+            AudioFormat::iLBC                 => (id::ILBC, None),
+            AudioFormat::DVIIntelIMA          => (id::DVI_INTEL_IMA, None),
+            AudioFormat::MicrosoftGSM         => (id::MICROSOFT_GSM, None),
+            AudioFormat::AES3                 => (id::AES3, None),
+            AudioFormat::EnhancedAC3          => (id::ENHANCED_AC3, None),
+            AudioFormat::FLAC(flag)           => (id::FLAC, Some(flag.bits())),
+            AudioFormat::Opus                 => (id::OPUS, None),
+            AudioFormat::LatmInLoas           => (id::LATM_IN_LOAS, None),
+            AudioFormat::MPEGD_USAC           => (id::MPEGD_USAC, None),
+            AudioFormat::APAC                 => (id::APAC, None),
+            AudioFormat::Other(format, flag)  => (format, Some(flag)),
         }
     }
 
 }
 
 
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AudioFormat::Other(format, _) => fmt::Display::fmt(&FourCC(format), f),
+            ref format => write!(f, "{:?}", format),
+        }
+    }
+}
+
+
 bitflags! {
     /// Standard flags for use in the **F60958AC3** **AudioFormat** variant.
     ///
@@ -376,6 +518,28 @@ bitflags! {
     }
 }
 
+impl LinearPcmFlags {
+
+    /// The number of fractional bits stored within the `FLAGS_SAMPLE_FRACTION_MASK` bitfield.
+    ///
+    /// A non-zero value indicates that an integer format is to be interpreted as fixed point.
+    pub fn sample_fraction_bits(&self) -> u32 {
+        let mask = LinearPcmFlags::FLAGS_SAMPLE_FRACTION_MASK.bits();
+        let shift = LinearPcmFlags::FLAGS_SAMPLE_FRACTION_SHIFT.bits();
+        (self.bits() & mask) >> shift
+    }
+
+    /// Produce a copy of the flags with the `FLAGS_SAMPLE_FRACTION_MASK` bitfield set to the
+    /// given number of fractional bits.
+    pub fn with_sample_fraction_bits(&self, fraction_bits: u32) -> Self {
+        let mask = LinearPcmFlags::FLAGS_SAMPLE_FRACTION_MASK.bits();
+        let shift = LinearPcmFlags::FLAGS_SAMPLE_FRACTION_SHIFT.bits();
+        let bits = (self.bits() & !mask) | ((fraction_bits << shift) & mask);
+        retain_bits!(LinearPcmFlags, bits)
+    }
+
+}
+
 bitflags! {
     /// Flags set for Apple Lossless data.
     ///
@@ -399,6 +563,27 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags set for FLAC data, indicating the bit depth of the source data.
+    ///
+    /// **Available** in OS X v10.13 and later.
+    ///
+    /// Note: In the original Core Audio API these are consolidated with what we have named the
+    /// **StandardFlags** and **AppleLosslessFlags** types under the `AudioFormatFlag` type. We
+    /// have chosen to separate these for greater type safety and clearer compatibility with
+    /// the **AudioFormat** type.
+    pub struct FlacFlags: u32 {
+        /// Sourced from 16 bit native endian signed integer data.
+        const BIT_16_SOURCE_DATA = 1;
+        /// Sourced from 20 bit native endian signed integer data aligned high in 24 bits.
+        const BIT_20_SOURCE_DATA = 2;
+        /// Sourced from 24 bit native endian signed integer data.
+        const BIT_24_SOURCE_DATA = 3;
+        /// Sourced from 32 bit native endian signed integer data.
+        const BIT_32_SOURCE_DATA = 4;
+    }
+}
+
 /// "Used in the `mFormatFlags` field of an `AudioStreamBasicDescription` structure that
 /// describes an MPEG-4 audio stream to specify the type of MPEG-4 audio data.
 ///
@@ -0,0 +1,84 @@
+//! Handling of the Core Foundation types returned by the Audio Unit API.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use sys;
+
+
+/// Copy the contents of the given `CFStringRef` into a `String`.
+///
+/// Returns `None` if the string is null or cannot be represented as UTF-8.
+///
+/// The `CFStringRef` is *not* released.
+pub unsafe fn to_string(cf_string: sys::CFStringRef) -> Option<String> {
+    if cf_string.is_null() {
+        return None;
+    }
+    let encoding = sys::kCFStringEncodingUTF8;
+    let len = sys::CFStringGetLength(cf_string);
+    // Leave room for the nul terminator.
+    let size = sys::CFStringGetMaximumSizeForEncoding(len, encoding) + 1;
+    let mut buffer = vec![0 as c_char; size as usize];
+    if sys::CFStringGetCString(cf_string, buffer.as_mut_ptr(), size, encoding) == 0 {
+        return None;
+    }
+    let c_str = CStr::from_ptr(buffer.as_ptr());
+    c_str.to_str().ok().map(|s| s.to_owned())
+}
+
+/// The same as `to_string` but releases the `CFStringRef` afterwards.
+///
+/// Use this for strings returned by the "Copy" functions, which the caller owns.
+pub unsafe fn to_string_and_release(cf_string: sys::CFStringRef) -> Option<String> {
+    let string = to_string(cf_string);
+    if !cf_string.is_null() {
+        sys::CFRelease(cf_string as sys::CFTypeRef);
+    }
+    string
+}
+
+
+/// An owned `CFDictionaryRef`, as returned by properties such as `ClassInfo`.
+///
+/// The dictionary is released when the **CFDictionary** is dropped.
+#[derive(Debug)]
+pub struct CFDictionary {
+    dictionary: sys::CFDictionaryRef,
+}
+
+impl CFDictionary {
+
+    /// Take ownership of the given `CFDictionaryRef`, which will be released on drop.
+    ///
+    /// Returns `None` if the dictionary is null.
+    pub unsafe fn from_owned_ref(dictionary: sys::CFDictionaryRef) -> Option<Self> {
+        if dictionary.is_null() {
+            None
+        } else {
+            Some(CFDictionary { dictionary: dictionary })
+        }
+    }
+
+    /// The underlying `CFDictionaryRef`.
+    ///
+    /// The dictionary is only valid for as long as the **CFDictionary** is alive.
+    pub fn as_ptr(&self) -> sys::CFDictionaryRef {
+        self.dictionary
+    }
+
+}
+
+impl Clone for CFDictionary {
+    fn clone(&self) -> Self {
+        unsafe { sys::CFRetain(self.dictionary as sys::CFTypeRef); }
+        CFDictionary { dictionary: self.dictionary }
+    }
+}
+
+impl Drop for CFDictionary {
+    fn drop(&mut self) {
+        unsafe { sys::CFRelease(self.dictionary as sys::CFTypeRef); }
+    }
+}
+
+unsafe impl Send for CFDictionary {}
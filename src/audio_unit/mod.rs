@@ -2,55 +2,92 @@
 //!
 //! Learn more about the Audio Unit API [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Conceptual/AudioUnitProgrammingGuide/Introduction/Introduction.html#//apple_ref/doc/uid/TP40003278-CH1-SW2)
 //! and [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Conceptual/AudioUnitProgrammingGuide/TheAudioUnit/TheAudioUnit.html).
-//!
-//! TODO: The following are `kAudioUnitSubType`s (along with their const u32) generated by
-//! rust-bindgen that we could not find any documentation on:
-//!
-//! - MIDISynth            = 1836284270,
-//! - RoundTripAAC         = 1918984547,
-//! - SpatialMixer         = 862217581,
-//! - SphericalHeadPanner  = 1936746610,
-//! - VectorPanner         = 1986158963,
-//! - SoundFieldPanner     = 1634558569,
-//! - HRTFPanner           = 1752331366,
-//! - NetReceive           = 1852990326,
-//!
-//! If you can find documentation on these, please feel free to submit an issue or PR with the
-//! fixes!
 
 
-use error::Error;
+use error::{AudioUnitError, Error};
+use self::audio_component::MANUFACTURER_APPLE;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::AtomicI32;
 use std::os::raw::{c_uint, c_void};
 use sys;
 
+pub use self::audio_buffer_list::AudioBufferListBuf;
+pub use self::audio_component::{AudioComponent, AudioComponentFlags, ComponentDescription};
 pub use self::audio_format::AudioFormat;
-pub use self::sample_format::{SampleFormat, Sample};
-pub use self::stream_format::StreamFormat;
+pub use self::cf::CFDictionary;
+pub use self::parameter::{
+    Parameter,
+    ParameterEvent,
+    ParameterEventKind,
+    ParameterFlags,
+    ParameterInfo,
+    ParameterScheduler,
+    ParameterUnit,
+};
+pub use self::property::{Pod, Property, Settable};
+pub use self::property_listener::PropertyListener;
+pub use self::sample_format::{SampleFormat, Sample, I24, Fixed8_24, Tpdf};
+pub use self::stream_description::AudioStreamDescription;
+pub use self::stream_format::{StreamFormat, Layout};
 pub use self::types::{
     Type,
     EffectType,
     FormatConverterType,
     GeneratorType,
     IOType,
+    MidiProcessorType,
     MixerType,
     MusicDeviceType,
+    MusicEffectType,
+    OfflineEffectType,
+    PannerType,
 };
 
 
+// Construct a flags type from the given bits, retaining any bits that are unknown to the type.
+//
+// This is safe as none of the flags types place any invariants upon their bits.
+macro_rules! retain_bits {
+    ($Flags:ident, $bits:expr) => (unsafe { $Flags::from_bits_unchecked($bits) })
+}
+
+
+pub mod audio_buffer_list;
+pub mod audio_component;
 pub mod audio_format;
+mod cf;
+pub mod parameter;
+pub mod property;
+pub mod property_listener;
 pub mod render_callback;
 pub mod sample_format;
+pub mod stream_description;
 pub mod stream_format;
 pub mod types;
 
 
+/// The size of an **AudioUnit** property's value and whether or not it is writable.
+///
+/// Returned by [**AudioUnit::get_property_info**](./struct.AudioUnit#method.get_property_info).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PropertyInfo {
+    /// The size of the property's value in bytes.
+    pub size: usize,
+    /// Whether or not the property may be set.
+    pub writable: bool,
+}
+
+
 /// The input and output **Scope**s.
 ///
 /// More info [here](https://developer.apple.com/library/ios/documentation/AudioUnit/Reference/AudioUnitPropertiesReference/index.html#//apple_ref/doc/constant_group/Audio_Unit_Scopes)
 /// and [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Conceptual/AudioUnitProgrammingGuide/TheAudioUnit/TheAudioUnit.html).
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Scope {
     Global = 0,
     Input  = 1,
@@ -62,29 +99,159 @@ pub enum Scope {
     LayerItem = 7,
 }
 
-/// Represents the **Input** and **Output** **Element**s.
+/// An **Element** (aka bus) of an **AudioUnit**, identified by its index within a scope.
 ///
-/// These are used when specifying which **Element** we're setting the properties of.
-#[derive(Copy, Clone, Debug)]
-pub enum Element {
-    Output = 0,
-    Input  = 1,
+/// These are used when specifying which **Element** we're setting the properties of. I/O units
+/// use `Element::Output` (bus `0`) for output and `Element::Input` (bus `1`) for input, while
+/// units such as mixers may have any number of elements within a scope (see
+/// [**AudioUnit::element_count**](./struct.AudioUnit#method.element_count)).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Element(pub u32);
+
+#[allow(non_upper_case_globals)]
+impl Element {
+    /// The output element (bus `0`) of an I/O unit.
+    pub const Output: Element = Element(0);
+    /// The input element (bus `1`) of an I/O unit.
+    pub const Input: Element = Element(1);
+
+    /// The element with the given bus index.
+    pub const fn bus(index: u32) -> Self {
+        Element(index)
+    }
+
+    /// The bus index of the element.
+    pub const fn to_u32(&self) -> u32 {
+        self.0
+    }
 }
 
+impl From<u32> for Element {
+    fn from(index: u32) -> Self {
+        Element(index)
+    }
+}
 
-/// A rust representation of the sys::AudioUnit, including a pointer to the current rendering callback.
+impl From<Element> for u32 {
+    fn from(elem: Element) -> Self {
+        elem.0
+    }
+}
+
+
+/// A rust representation of the sys::AudioUnit, including pointers to the current rendering callbacks.
+///
+/// The `S` type parameter tracks whether or not the audio unit has been initialized (see
+/// [**Initialized**](./enum.Initialized) and [**Uninitialized**](./enum.Uninitialized)). This
+/// ensures that the typed properties that must be set before `AudioUnitInitialize` (see
+/// [**property::Settable**](./property/trait.Settable)) can only be set on an uninitialized unit,
+/// and that a unit can only be started once it is initialized.
 ///
 /// Find the original Audio Unit Programming Guide [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Conceptual/AudioUnitProgrammingGuide/TheAudioUnit/TheAudioUnit.html).
-pub struct AudioUnit {
+pub struct AudioUnit<S = Initialized> {
     instance: sys::AudioUnit,
-    maybe_render_callback: Option<*mut render_callback::InputProcFnWrapper>,
-    maybe_input_callback: Option<InputCallback>,
+    // Whether or not the unit is an I/O unit. Only I/O units may be stopped.
+    is_io: bool,
+    // The render callback installed on each element, by element.
+    render_callbacks: HashMap<Element, *mut render_callback::InputProcFnWrapper>,
+    maybe_input_callback: Option<*mut render_callback::InputProcFnWrapper>,
+    // Render and input callbacks that have been replaced or removed. Core Audio may still be
+    // calling them, so they are freed once the instance is disposed of.
+    retired_callbacks: Vec<*mut render_callback::InputProcFnWrapper>,
+    // Grow the buffers that the input callback renders into whenever the maximum number of
+    // frames per render changes, so that they never need to be grown on the render thread.
+    input_buffer_listeners: Vec<PropertyListener>,
+    // Shared with any `PropertyListener`s and `ParameterScheduler`s so that they know whether or
+    // not the instance has been disposed of. Only write-locked while disposing.
+    is_alive: Arc<RwLock<bool>>,
+    // The closures of `PropertyListener`s that were still being called when they were removed,
+    // which are freed once the instance is disposed of.
+    retired_listeners: property_listener::RetiredListeners,
+    // The `OSStatus` of the most recent error returned by a render or input callback, or `0`.
+    // Shared with the callbacks, which must not block.
+    last_callback_error: Arc<AtomicI32>,
+    // The payload of the first panic within a render or input callback. Shared with the
+    // callbacks.
+    callback_panic: render_callback::PanicPayload,
+    state: PhantomData<S>,
 }
 
-struct InputCallback {
-    // The audio buffer list to which input data is rendered.
-    buffer_list: *mut sys::AudioBufferList,
-    callback: *mut render_callback::InputProcFnWrapper,
+/// The state of an **AudioUnit** that has been initialized via `AudioUnitInitialize` and is ready
+/// to be started.
+///
+/// This is the default state of an **AudioUnit**.
+pub enum Initialized {}
+
+/// The state of an **AudioUnit** that has not yet been initialized.
+///
+/// Use this state to set properties that must be configured before initialization, such as
+/// `EnableIO` or `MaximumFramesPerSlice`, before calling
+/// [**initialize**](./struct.AudioUnit#method.initialize).
+///
+/// ```no_run
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, Element, IOType, Scope};
+/// # use coreaudio::audio_unit::property::EnableIO;
+/// # fn main() -> Result<(), coreaudio::Error> {
+/// let mut audio_unit = AudioUnit::new_uninitialized(IOType::HalOutput)?;
+/// audio_unit.set_scoped(EnableIO, Scope::Input, Element::Input, &1)?;
+/// let mut audio_unit = audio_unit.initialize()?;
+/// audio_unit.start()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// An uninitialized **AudioUnit** cannot be started.
+///
+/// ```compile_fail
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, IOType};
+/// # fn main() {
+/// let mut audio_unit = AudioUnit::new_uninitialized(IOType::DefaultOutput).unwrap();
+/// audio_unit.start().unwrap();
+/// # }
+/// ```
+pub enum Uninitialized {}
+
+
+/// The error returned when [**AudioUnit::initialize**](./struct.AudioUnit#method.initialize)
+/// fails.
+///
+/// The uninitialized **AudioUnit** is returned along with the error, so that it may be
+/// reconfigured and initialized again without losing its callbacks and listeners.
+pub struct InitializeError {
+    error: Error,
+    audio_unit: AudioUnit<Uninitialized>,
+}
+
+impl InitializeError {
+    /// The error that occurred while initializing.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Take back the uninitialized **AudioUnit**.
+    pub fn into_inner(self) -> AudioUnit<Uninitialized> {
+        self.audio_unit
+    }
+}
+
+impl fmt::Debug for InitializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InitializeError").field("error", &self.error).finish()
+    }
+}
+
+impl fmt::Display for InitializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl From<InitializeError> for Error {
+    fn from(err: InitializeError) -> Self {
+        err.error
+    }
 }
 
 
@@ -107,17 +274,26 @@ impl AudioUnit {
     /// - [**FormatConverterType**](./types/enum.FormatConverterType)
     /// - [**EffectType**](./types/enum.EffectType)
     /// - [**MixerType**](./types/enum.MixerType)
+    /// - [**PannerType**](./types/enum.PannerType)
+    /// - [**OfflineEffectType**](./types/enum.OfflineEffectType)
     ///
     /// To construct the **AudioUnit** with some component flags, see
     /// [**AudioUnit::new_with_flags**](./struct.AudioUnit#method.new_with_flags).
     ///
+    /// The returned **AudioUnit** is already initialized. To set properties that must be
+    /// configured before initialization, see
+    /// [**AudioUnit::new_uninitialized**](./struct.AudioUnit#method.new_uninitialized).
+    ///
     /// Note: the `AudioUnit` is constructed with the `kAudioUnitManufacturer_Apple` Manufacturer
     /// Identifier, as this is the only Audio Unit Manufacturer Identifier documented by Apple in
     /// the AudioUnit reference (see [here](https://developer.apple.com/library/prerelease/mac/documentation/AudioUnit/Reference/AUComponentServicesReference/index.html#//apple_ref/doc/constant_group/Audio_Unit_Manufacturer_Identifier)).
+    /// To construct an `AudioUnit` from another manufacturer, see
+    /// [**AudioUnit::from_component**](./struct.AudioUnit#method.from_component).
     pub fn new<T>(ty: T) -> Result<AudioUnit, Error>
         where T: Into<Type>,
     {
-        AudioUnit::new_with_flags(ty, 0, 0)
+        let audio_unit = try!(AudioUnit::new_uninitialized(ty));
+        Ok(try!(audio_unit.initialize()))
     }
 
     /// The same as [**AudioUnit::new**](./struct.AudioUnit#method.new) but with the given
@@ -125,52 +301,218 @@ impl AudioUnit {
     pub fn new_with_flags<T>(ty: T, flags: u32, mask: u32) -> Result<AudioUnit, Error>
         where T: Into<Type>,
     {
-        const MANUFACTURER_IDENTIFIER: u32 = sys::kAudioUnitManufacturer_Apple;
+        let audio_unit = try!(AudioUnit::new_uninitialized_with_flags(ty, flags, mask));
+        Ok(try!(audio_unit.initialize()))
+    }
+
+    /// Construct a new AudioUnit from the given component.
+    ///
+    /// This allows for constructing audio units from any manufacturer, including third-party
+    /// units found via [**AudioComponent::find**](./audio_component/struct.AudioComponent#method.find).
+    pub fn from_component(component: AudioComponent) -> Result<AudioUnit, Error> {
+        let audio_unit = try!(AudioUnit::from_component_uninitialized(component));
+        Ok(try!(audio_unit.initialize()))
+    }
+
+}
+
+
+impl AudioUnit<Uninitialized> {
+
+    /// The same as [**AudioUnit::new**](./struct.AudioUnit#method.new) but without initializing
+    /// the **AudioUnit**, so that it may be configured before calling
+    /// [**initialize**](./struct.AudioUnit#method.initialize).
+    pub fn new_uninitialized<T>(ty: T) -> Result<AudioUnit<Uninitialized>, Error>
+        where T: Into<Type>,
+    {
+        AudioUnit::new_uninitialized_with_flags(ty, 0, 0)
+    }
+
+    /// The same as [**AudioUnit::new_with_flags**](./struct.AudioUnit#method.new_with_flags) but
+    /// without initializing the **AudioUnit**.
+    pub fn new_uninitialized_with_flags<T>(ty: T, flags: u32, mask: u32)
+        -> Result<AudioUnit<Uninitialized>, Error>
+        where T: Into<Type>,
+    {
         let au_type: Type = ty.into();
-        let sub_type_u32 = match au_type.to_subtype_u32() {
-            Some(u) => u,
-            None => return Err(Error::NoKnownSubtype),
-        };
+        if au_type.to_subtype_u32().is_none() {
+            return Err(Error::NoKnownSubtype);
+        }
 
         // A description of the audio unit we desire.
-        let desc = sys::AudioComponentDescription {
-            componentType: au_type.to_u32() as c_uint,
-            componentSubType: sub_type_u32 as c_uint,
-            componentManufacturer: MANUFACTURER_IDENTIFIER,
-            componentFlags: flags,
-            componentFlagsMask: mask,
+        let desc = ComponentDescription {
+            flags: retain_bits!(AudioComponentFlags, flags),
+            flags_mask: retain_bits!(AudioComponentFlags, mask),
+            ..ComponentDescription::from_type(au_type).manufacturer(MANUFACTURER_APPLE)
         };
 
-        unsafe {
-            // Find the default audio unit for the description.
-            //
-            // From the "Audio Unit Hosting Guide for iOS":
-            //
-            // Passing NULL to the first parameter of AudioComponentFindNext tells this function to
-            // find the first system audio unit matching the description, using a system-defined
-            // ordering. If you instead pass a previously found audio unit reference in this
-            // parameter, the function locates the next audio unit matching the description.
-            let component = sys::AudioComponentFindNext(ptr::null_mut(), &desc as *const _);
-            if component.is_null() {
-                return Err(Error::NoMatchingDefaultAudioUnitFound);
-            }
+        // Find the default audio unit for the description.
+        //
+        // From the "Audio Unit Hosting Guide for iOS":
+        //
+        // Passing NULL to the first parameter of AudioComponentFindNext tells this function to
+        // find the first system audio unit matching the description, using a system-defined
+        // ordering. If you instead pass a previously found audio unit reference in this
+        // parameter, the function locates the next audio unit matching the description.
+        match AudioComponent::find_first(&desc) {
+            Some(component) => AudioUnit::from_component_uninitialized(component),
+            None => Err(Error::NoMatchingDefaultAudioUnitFound),
+        }
+    }
 
-            // Create an instance of the default audio unit using the component.
+    /// The same as [**AudioUnit::from_component**](./struct.AudioUnit#method.from_component) but
+    /// without initializing the **AudioUnit**.
+    pub fn from_component_uninitialized(component: AudioComponent)
+        -> Result<AudioUnit<Uninitialized>, Error>
+    {
+        // If the type can't be determined, assume an I/O unit so that it is stopped before it is
+        // disposed of.
+        let is_io = component.ty().map(|ty| ty.is_io()).unwrap_or(true);
+        unsafe {
+            // Create an instance of the audio unit using the component.
             let mut instance: sys::AudioUnit = mem::uninitialized();
+            let component = component.as_sys();
             try_os_status!(
                 sys::AudioComponentInstanceNew(component, &mut instance as *mut sys::AudioUnit)
             );
-
-            // Initialise the audio unit!
-            try_os_status!(sys::AudioUnitInitialize(instance));
             Ok(AudioUnit {
                 instance: instance,
-                maybe_render_callback: None,
+                is_io: is_io,
+                render_callbacks: HashMap::new(),
                 maybe_input_callback: None,
+                retired_callbacks: Vec::new(),
+                input_buffer_listeners: Vec::new(),
+                is_alive: Arc::new(RwLock::new(true)),
+                retired_listeners: Arc::new(Mutex::new(Vec::new())),
+                last_callback_error: Arc::new(AtomicI32::new(0)),
+                callback_panic: Arc::new(Mutex::new(None)),
+                state: PhantomData,
             })
         }
     }
 
+    /// Dispose of the **AudioUnit** and free its render and input callbacks.
+    ///
+    /// Unlike dropping the **AudioUnit**, this reports any error that occurs while disposing.
+    ///
+    /// **Available** in OS X v10.6 and later.
+    pub fn close(mut self) -> Result<(), Error> {
+        self.dispose()
+    }
+
+    /// Initialize the **AudioUnit**, after which it may be started.
+    ///
+    /// If initialization fails, the uninitialized **AudioUnit** is returned within the
+    /// **InitializeError** so that it may be reconfigured and initialized again.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn initialize(self) -> Result<AudioUnit, InitializeError> {
+        let status = unsafe { sys::AudioUnitInitialize(self.instance) };
+        match Error::from_os_status(status) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err(InitializeError { error: error, audio_unit: self }),
+        }
+    }
+
+}
+
+
+impl AudioUnit<Initialized> {
+
+    /// Starts an I/O **AudioUnit**, which in turn starts the audio unit processing graph that it is
+    /// connected to.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn start(&mut self) -> Result<(), Error> {
+        unsafe { try_os_status!(sys::AudioOutputUnitStart(self.instance)); }
+        Ok(())
+    }
+
+    /// Stops an I/O **AudioUnit**, which in turn stops the audio unit processing graph that it is
+    /// connected to.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        unsafe { try_os_status!(sys::AudioOutputUnitStop(self.instance)); }
+        Ok(())
+    }
+
+    /// Uninitialize the **AudioUnit** so that it may be reconfigured.
+    ///
+    /// The **AudioUnit** should be stopped before it is uninitialized.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn uninitialize(self) -> Result<AudioUnit<Uninitialized>, Error> {
+        unsafe { try_os_status!(sys::AudioUnitUninitialize(self.instance)); }
+        Ok(self.into_state())
+    }
+
+    /// Stop, uninitialize and dispose of the **AudioUnit**, and free its render and input
+    /// callbacks.
+    ///
+    /// Only I/O units are stopped, as other units can't be started in the first place.
+    ///
+    /// Every step is attempted even if an earlier one fails, so that the instance is never
+    /// leaked. The first error that occurred (if any) is returned.
+    ///
+    /// Unlike dropping the **AudioUnit**, this allows for handling errors during teardown.
+    pub fn close(mut self) -> Result<(), Error> {
+        let stopped = if self.is_io { self.stop() } else { Ok(()) };
+        let uninitialized = unsafe {
+            Error::from_os_status(sys::AudioUnitUninitialize(self.instance))
+        };
+        let disposed = self.dispose();
+        stopped.and(uninitialized).and(disposed)
+    }
+
+}
+
+
+impl<S> AudioUnit<S> {
+
+    // Move the instance and callbacks into an `AudioUnit` of a different state, without running
+    // the `Drop` implementation.
+    fn into_state<T>(mut self) -> AudioUnit<T> {
+        let audio_unit = AudioUnit {
+            instance: self.instance,
+            is_io: self.is_io,
+            render_callbacks: mem::replace(&mut self.render_callbacks, HashMap::new()),
+            maybe_input_callback: self.maybe_input_callback.take(),
+            retired_callbacks: mem::replace(&mut self.retired_callbacks, Vec::new()),
+            input_buffer_listeners: mem::replace(&mut self.input_buffer_listeners, Vec::new()),
+            // Moved out rather than cloned, as `self` is forgotten.
+            is_alive: unsafe { ptr::read(&self.is_alive) },
+            retired_listeners: unsafe { ptr::read(&self.retired_listeners) },
+            last_callback_error: unsafe { ptr::read(&self.last_callback_error) },
+            callback_panic: unsafe { ptr::read(&self.callback_panic) },
+            state: PhantomData,
+        };
+        mem::forget(self);
+        audio_unit
+    }
+
+    // Dispose of the instance and free the callbacks.
+    //
+    // The instance is set to null so that `Drop` knows that it has already been disposed of.
+    fn dispose(&mut self) -> Result<(), Error> {
+        let status = {
+            let mut is_alive = self.is_alive.write().unwrap_or_else(|err| err.into_inner());
+            *is_alive = false;
+            unsafe { sys::AudioComponentInstanceDispose(self.instance) }
+        };
+        self.instance = ptr::null_mut();
+        // The listeners can no longer be called, so their closures may be freed.
+        self.retired_listeners.lock().unwrap_or_else(|err| err.into_inner()).clear();
+        // Likewise for the callbacks, including those that have been retired.
+        let callbacks = self.render_callbacks.drain().map(|(_, callback)| callback)
+            .chain(self.maybe_input_callback.take())
+            .chain(self.retired_callbacks.drain(..));
+        for callback in callbacks {
+            unsafe { drop(Box::from_raw(callback)); }
+        }
+        Error::from_os_status(status)
+    }
+
     /// Sets the value for some property of the **AudioUnit**.
     ///
     /// To clear an audio unit property value, set the data paramater with `None::<()>`.
@@ -204,40 +546,144 @@ impl AudioUnit {
     /// - **id**: The identifier of the property.
     /// - **scope**: The audio unit scope for the property.
     /// - **elem**: The audio unit element for the property.
-    pub fn get_property<T>(&self, id: u32, scope: Scope, elem: Element) -> Result<T, Error> {
+    pub fn get_property<T>(&self, id: u32, scope: Scope, elem: Element) -> Result<T, Error>
+        where T: Pod,
+    {
         get_property(self.instance, id, scope, elem)
     }
 
-    /// Starts an I/O **AudioUnit**, which in turn starts the audio unit processing graph that it is
-    /// connected to.
+    /// Gets the size of an **AudioUnit** property's value and whether or not it is writable.
     ///
-    /// **Available** in OS X v10.0 and later.
-    pub fn start(&mut self) -> Result<(), Error> {
-        unsafe { try_os_status!(sys::AudioOutputUnitStart(self.instance)); }
-        Ok(())
+    /// **Available** in iOS 2.0 and later.
+    pub fn get_property_info(&self, id: u32, scope: Scope, elem: Element)
+        -> Result<PropertyInfo, Error>
+    {
+        get_property_info(self.instance, id, scope, elem)
     }
 
-    /// Stops an I/O **AudioUnit**, which in turn stops the audio unit processing graph that it is
-    /// connected to.
+    /// Gets the value of an **AudioUnit** property that consists of a variable number of `T`s,
+    /// such as `SupportedNumChannels` or `ParameterList`.
+    pub fn get_property_vec<T>(&self, id: u32, scope: Scope, elem: Element)
+        -> Result<Vec<T>, Error>
+        where T: Pod,
+    {
+        get_property_vec(self.instance, id, scope, elem)
+    }
+
+    /// Gets the raw bytes of an **AudioUnit** property's value, for properties whose values are
+    /// structs with trailing variable-length data such as `AudioChannelLayout`.
+    pub fn get_property_bytes(&self, id: u32, scope: Scope, elem: Element)
+        -> Result<Vec<u8>, Error>
+    {
+        get_property_bytes(self.instance, id, scope, elem)
+    }
+
+    /// Gets the value of an **AudioUnit** property whose value is a `CFStringRef`, such as
+    /// `ElementName`.
+    pub fn get_property_cf_string(&self, id: u32, scope: Scope, elem: Element)
+        -> Result<Option<String>, Error>
+    {
+        get_property_cf_string(self.instance, id, scope, elem)
+    }
+
+    /// Gets the value of an **AudioUnit** property whose value is a `CFDictionaryRef`, such as
+    /// `ClassInfo`.
+    pub fn get_property_cf_dictionary(&self, id: u32, scope: Scope, elem: Element)
+        -> Result<Option<CFDictionary>, Error>
+    {
+        get_property_cf_dictionary(self.instance, id, scope, elem)
+    }
+
+    /// Gets the value of the given typed property on its default scope and element.
     ///
-    /// **Available** in OS X v10.0 and later.
-    pub fn stop(&mut self) -> Result<(), Error> {
-        unsafe { try_os_status!(sys::AudioOutputUnitStop(self.instance)); }
-        Ok(())
+    /// Returns `Error::PropertySizeDoesNotMatchType` if the size of the value returned by the
+    /// **AudioUnit** does not match the size of the property's `Value` type.
+    ///
+    /// ```no_run
+    /// # extern crate coreaudio;
+    /// # use coreaudio::audio_unit::{AudioUnit, IOType};
+    /// # use coreaudio::audio_unit::property::SampleRate;
+    /// # fn main() -> Result<(), coreaudio::Error> {
+    /// let audio_unit = AudioUnit::new(IOType::DefaultOutput)?;
+    /// let sample_rate: f64 = audio_unit.get(SampleRate)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get<P>(&self, property: P) -> Result<P::Value, Error>
+        where P: Property,
+    {
+        self.get_scoped(property, P::SCOPE, P::ELEMENT)
+    }
+
+    /// The same as [**get**](./struct.AudioUnit#method.get) but on the given scope and element.
+    pub fn get_scoped<P>(&self, _property: P, scope: Scope, elem: Element)
+        -> Result<P::Value, Error>
+        where P: Property,
+    {
+        self.get_property(P::ID, scope, elem)
+    }
+
+    /// Sets the value of the given typed property on its default scope and element.
+    ///
+    /// Properties that must be set before initialization may only be set on an
+    /// `AudioUnit<Uninitialized>` (see [**property::Settable**](./property/trait.Settable)).
+    pub fn set<P>(&mut self, property: P, value: &P::Value) -> Result<(), Error>
+        where P: Settable<S>,
+    {
+        self.set_scoped(property, P::SCOPE, P::ELEMENT, value)
+    }
+
+    /// The same as [**set**](./struct.AudioUnit#method.set) but on the given scope and element.
+    pub fn set_scoped<P>(&mut self, _property: P, scope: Scope, elem: Element, value: &P::Value)
+        -> Result<(), Error>
+        where P: Settable<S>,
+    {
+        self.set_property(P::ID, scope, elem, Some(value))
+    }
+
+    /// The combinations of input and output channel counts supported by the **AudioUnit**.
+    ///
+    /// A value of `-1` indicates any number of channels, while `-2` indicates any number of
+    /// channels as long as the input and output counts match. An empty `Vec` indicates that the
+    /// **AudioUnit** supports any combination.
+    pub fn supported_num_channels(&self) -> Result<Vec<sys::AUChannelInfo>, Error> {
+        let id = sys::kAudioUnitProperty_SupportedNumChannels;
+        match self.get_property_vec(id, Scope::Global, Element::Output) {
+            Err(Error::AudioUnit(AudioUnitError::InvalidProperty)) => Ok(vec![]),
+            result => result,
+        }
+    }
+
+    /// The number of elements (aka buses) within the given scope.
+    ///
+    /// For some units, such as `MixerType::MultiChannelMixer`, this may be set on the `Input`
+    /// scope before initialization via `set(property::ElementCount, ..)`.
+    pub fn element_count(&self, scope: Scope) -> Result<u32, Error> {
+        self.get_scoped(property::ElementCount, scope, Element::Output)
+    }
+
+    /// The name of the given element, if it has one.
+    pub fn element_name(&self, scope: Scope, elem: Element) -> Result<Option<String>, Error> {
+        self.get_property_cf_string(sys::kAudioUnitProperty_ElementName, scope, elem)
+    }
+
+    /// The **AudioUnit**'s current state, as a property list dictionary suitable for saving as a
+    /// preset.
+    pub fn class_info(&self) -> Result<Option<CFDictionary>, Error> {
+        let id = sys::kAudioUnitProperty_ClassInfo;
+        self.get_property_cf_dictionary(id, Scope::Global, Element::Output)
     }
 
     /// Set the **AudioUnit**'s sample rate.
     ///
     /// **Available** in iOS 2.0 and later.
     pub fn set_sample_rate(&mut self, sample_rate: f64) -> Result<(), Error> {
-        let id = sys::kAudioUnitProperty_SampleRate;
-        self.set_property(id, Scope::Input, Element::Output, Some(&sample_rate))
+        self.set(property::SampleRate, &sample_rate)
     }
 
     /// Get the **AudioUnit**'s sample rate.
     pub fn sample_rate(&self) -> Result<f64, Error> {
-        let id = sys::kAudioUnitProperty_SampleRate;
-        self.get_property(id, Scope::Input, Element::Output)
+        self.get(property::SampleRate)
     }
 
     /// Sets the current **StreamFormat** for the AudioUnit.
@@ -258,49 +704,60 @@ impl AudioUnit {
         &mut self,
         stream_format: StreamFormat,
         scope: Scope,
+        elem: Element,
     ) -> Result<(), Error> {
-        let id = sys::kAudioUnitProperty_StreamFormat;
         let asbd = stream_format.to_asbd();
-        self.set_property(id, scope, Element::Output, Some(&asbd))
+        self.set_scoped(property::StreamFormat, scope, elem, &asbd)
     }
 
-    /// Return the current Stream Format for the AudioUnit.
-    pub fn stream_format(&self, scope: Scope) -> Result<StreamFormat, Error> {
-        let id = sys::kAudioUnitProperty_StreamFormat;
-        let asbd = try!(self.get_property(id, scope, Element::Output));
+    /// Return the current Stream Format for the given scope and element of the AudioUnit.
+    pub fn stream_format(&self, scope: Scope, elem: Element) -> Result<StreamFormat, Error> {
+        let asbd = try!(self.get_scoped(property::StreamFormat, scope, elem));
         StreamFormat::from_asbd(asbd)
     }
 
-    /// Return the current output Stream Format for the AudioUnit.
+    /// Return the current output Stream Format for the AudioUnit's `Output` element.
     pub fn output_stream_format(&self) -> Result<StreamFormat, Error> {
-        self.stream_format(Scope::Output)
+        self.stream_format(Scope::Output, Element::Output)
     }
 
-    /// Return the current input Stream Format for the AudioUnit.
+    /// Return the current input Stream Format for the AudioUnit's `Output` element.
+    ///
+    /// This is the format of the data provided by the render callback.
     pub fn input_stream_format(&self) -> Result<StreamFormat, Error> {
-        self.stream_format(Scope::Input)
+        self.stream_format(Scope::Input, Element::Output)
     }
 }
 
 
-unsafe impl Send for AudioUnit {}
+unsafe impl<S> Send for AudioUnit<S> {}
 
 
-impl Drop for AudioUnit {
-    fn drop(&mut self) {
-        unsafe {
-            use error;
+impl<S> AsRef<sys::AudioUnit> for AudioUnit<S> {
+    fn as_ref(&self) -> &sys::AudioUnit {
+        &self.instance
+    }
+}
 
-            // We don't want to panic in `drop`, so we'll ignore returned errors.
-            //
-            // A user should explicitly terminate the `AudioUnit` if they want to handle errors (we
-            // still need to provide a way to actually do that).
-            self.stop().ok();
-            error::Error::from_os_status(sys::AudioUnitUninitialize(self.instance)).ok();
 
-            self.free_render_callback();
-            self.free_input_callback();
+impl<S> Drop for AudioUnit<S> {
+    fn drop(&mut self) {
+        // The instance has already been disposed of via `close`.
+        if self.instance.is_null() {
+            return;
+        }
+
+        // We don't want to panic in `drop`, so we'll ignore returned errors.
+        //
+        // A user should explicitly `close` the `AudioUnit` if they want to handle errors. Stopping
+        // and uninitializing an uninitialized unit fails harmlessly.
+        unsafe {
+            if self.is_io {
+                Error::from_os_status(sys::AudioOutputUnitStop(self.instance)).ok();
+            }
+            Error::from_os_status(sys::AudioUnitUninitialize(self.instance)).ok();
         }
+        self.dispose().ok();
     }
 }
 
@@ -337,7 +794,7 @@ pub fn set_property<T>(
         (ptr, size)
     }).unwrap_or_else(|| (::std::ptr::null(), 0));
     let scope = scope as c_uint;
-    let elem = elem as c_uint;
+    let elem = elem.to_u32();
     unsafe {
         try_os_status!(sys::AudioUnitSetProperty(au, id, scope, elem, data_ptr, size))
     }
@@ -355,23 +812,179 @@ pub fn set_property<T>(
 /// - **id**: The identifier of the property.
 /// - **scope**: The audio unit scope for the property.
 /// - **elem**: The audio unit element for the property.
+///
+/// Returns `Error::PropertySizeDoesNotMatchType` if the size of the value returned by the
+/// **AudioUnit** does not match the size of `T`.
 pub fn get_property<T>(
     au: sys::AudioUnit,
     id: u32,
     scope: Scope,
     elem: Element,
 ) -> Result<T, Error>
+    where T: Pod,
 {
     let scope = scope as c_uint;
-    let elem = elem as c_uint;
-    let mut size = ::std::mem::size_of::<T>() as u32;
+    let elem = elem.to_u32();
+    let expected_size = ::std::mem::size_of::<T>() as u32;
+    let mut size = expected_size;
     unsafe {
-        let mut data: T = ::std::mem::uninitialized();
+        let mut data: T = ::std::mem::zeroed();
         let data_ptr = &mut data as *mut _ as *mut c_void;
         let size_ptr = &mut size as *mut _;
         try_os_status!(
             sys::AudioUnitGetProperty(au, id, scope, elem, data_ptr, size_ptr)
         );
+        if size != expected_size {
+            return Err(Error::PropertySizeDoesNotMatchType);
+        }
         Ok(data)
     }
 }
+
+/// Gets the size of an **AudioUnit** property's value and whether or not it is writable.
+///
+/// **Available** in iOS 2.0 and later.
+///
+/// Parameters
+/// ----------
+///
+/// - **au**: The AudioUnit instance.
+/// - **id**: The identifier of the property.
+/// - **scope**: The audio unit scope for the property.
+/// - **elem**: The audio unit element for the property.
+pub fn get_property_info(
+    au: sys::AudioUnit,
+    id: u32,
+    scope: Scope,
+    elem: Element,
+) -> Result<PropertyInfo, Error>
+{
+    let scope = scope as c_uint;
+    let elem = elem.to_u32();
+    let mut size: u32 = 0;
+    let mut writable: sys::Boolean = 0;
+    unsafe {
+        try_os_status!(
+            sys::AudioUnitGetPropertyInfo(au, id, scope, elem, &mut size, &mut writable)
+        );
+    }
+    Ok(PropertyInfo {
+        size: size as usize,
+        writable: writable != 0,
+    })
+}
+
+/// Gets the value of an **AudioUnit** property that consists of a variable number of `T`s, such
+/// as `SupportedNumChannels` or `ParameterList`.
+///
+/// Returns `Error::PropertySizeDoesNotMatchType` if the size of the value is not a multiple of the
+/// size of `T`.
+///
+/// Parameters
+/// ----------
+///
+/// - **au**: The AudioUnit instance.
+/// - **id**: The identifier of the property.
+/// - **scope**: The audio unit scope for the property.
+/// - **elem**: The audio unit element for the property.
+pub fn get_property_vec<T>(
+    au: sys::AudioUnit,
+    id: u32,
+    scope: Scope,
+    elem: Element,
+) -> Result<Vec<T>, Error>
+    where T: Pod,
+{
+    let info = try!(get_property_info(au, id, scope, elem));
+    let t_size = mem::size_of::<T>();
+    if t_size == 0 || info.size % t_size != 0 {
+        return Err(Error::PropertySizeDoesNotMatchType);
+    }
+    if info.size == 0 {
+        return Ok(Vec::new());
+    }
+    let len = info.size / t_size;
+    let mut data: Vec<T> = Vec::with_capacity(len);
+    let mut size = info.size as u32;
+    unsafe {
+        let data_ptr = data.as_mut_ptr() as *mut c_void;
+        try_os_status!(
+            sys::AudioUnitGetProperty(au, id, scope as c_uint, elem.to_u32(), data_ptr, &mut size)
+        );
+        // The size may have shrunk between calls, but never beyond the allocated capacity.
+        let size = ::std::cmp::min(size as usize, info.size);
+        data.set_len(size / t_size);
+    }
+    Ok(data)
+}
+
+/// Gets the raw bytes of an **AudioUnit** property's value.
+///
+/// This is useful for properties whose values are structs with trailing variable-length data,
+/// such as `AudioChannelLayout` or `CocoaUI`. Note that the bytes are not necessarily aligned for
+/// the struct, so they should be read with `ptr::read_unaligned`.
+///
+/// Parameters
+/// ----------
+///
+/// - **au**: The AudioUnit instance.
+/// - **id**: The identifier of the property.
+/// - **scope**: The audio unit scope for the property.
+/// - **elem**: The audio unit element for the property.
+pub fn get_property_bytes(
+    au: sys::AudioUnit,
+    id: u32,
+    scope: Scope,
+    elem: Element,
+) -> Result<Vec<u8>, Error>
+{
+    get_property_vec(au, id, scope, elem)
+}
+
+/// Gets the value of an **AudioUnit** property whose value is a `CFStringRef` owned by the
+/// caller, such as `ElementName`.
+///
+/// The string is released after it is copied into the returned `String`. Returns `None` if the
+/// property is not set (e.g. an element that has no name).
+///
+/// Parameters
+/// ----------
+///
+/// - **au**: The AudioUnit instance.
+/// - **id**: The identifier of the property.
+/// - **scope**: The audio unit scope for the property.
+/// - **elem**: The audio unit element for the property.
+pub fn get_property_cf_string(
+    au: sys::AudioUnit,
+    id: u32,
+    scope: Scope,
+    elem: Element,
+) -> Result<Option<String>, Error>
+{
+    let cf_string: sys::CFStringRef = try!(get_property(au, id, scope, elem));
+    Ok(unsafe { cf::to_string_and_release(cf_string) })
+}
+
+/// Gets the value of an **AudioUnit** property whose value is a `CFDictionaryRef` owned by the
+/// caller, such as `ClassInfo`.
+///
+/// The dictionary is released when the returned **CFDictionary** is dropped. Returns `None` if
+/// the property is not set.
+///
+/// Parameters
+/// ----------
+///
+/// - **au**: The AudioUnit instance.
+/// - **id**: The identifier of the property.
+/// - **scope**: The audio unit scope for the property.
+/// - **elem**: The audio unit element for the property.
+pub fn get_property_cf_dictionary(
+    au: sys::AudioUnit,
+    id: u32,
+    scope: Scope,
+    elem: Element,
+) -> Result<Option<CFDictionary>, Error>
+{
+    let dictionary: sys::CFDictionaryRef = try!(get_property(au, id, scope, elem));
+    Ok(unsafe { CFDictionary::from_owned_ref(dictionary) })
+}
@@ -0,0 +1,881 @@
+//! **AudioUnit** parameters, their discovery and typed parameter IDs for Apple's effect units.
+//!
+//! Parameters are the real-time controllable values of an **AudioUnit**, such as the cutoff
+//! frequency of a filter or the wet/dry mix of a delay. They may be read and written via
+//! [**AudioUnit::get_parameter**](../struct.AudioUnit#method.get_parameter) and
+//! [**AudioUnit::set_parameter**](../struct.AudioUnit#method.set_parameter), using either a raw
+//! `u32` parameter ID or one of the typed parameter enums within this module.
+//!
+//! Changes may also be scheduled at sample-accurate offsets within the next render cycle, either
+//! immediately or as linear ramps, via **ParameterEvent**s.
+//!
+//! Original documentation [here](https://developer.apple.com/documentation/audiotoolbox/audio_unit_parameters).
+
+use error::{AudioUnitError, Error};
+use std::mem;
+use std::os::raw::c_uint;
+use std::sync::{Arc, RwLock, TryLockError};
+use super::{cf, AudioUnit, Element, Scope};
+use sys;
+
+
+/// A value that identifies an **AudioUnit** parameter.
+///
+/// This is implemented for `u32` (the raw `AudioUnitParameterID`) as well as for each of the typed
+/// parameter enums within this module.
+pub trait Parameter {
+    /// The `AudioUnitParameterID` of the parameter.
+    fn id(&self) -> u32;
+}
+
+impl Parameter for u32 {
+    fn id(&self) -> u32 {
+        *self
+    }
+}
+
+
+/// The unit in which a parameter's value is expressed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParameterUnit {
+    Generic = 0,
+    Indexed = 1,
+    Boolean = 2,
+    Percent = 3,
+    Seconds = 4,
+    SampleFrames = 5,
+    Phase = 6,
+    Rate = 7,
+    Hertz = 8,
+    Cents = 9,
+    RelativeSemiTones = 10,
+    MIDINoteNumber = 11,
+    MIDIController = 12,
+    Decibels = 13,
+    LinearGain = 14,
+    Degrees = 15,
+    EqualPowerCrossfade = 16,
+    MixerFaderCurve1 = 17,
+    Pan = 18,
+    Meters = 19,
+    AbsoluteCents = 20,
+    Octaves = 21,
+    BPM = 22,
+    Beats = 23,
+    Milliseconds = 24,
+    Ratio = 25,
+    CustomUnit = 26,
+}
+
+impl ParameterUnit {
+    /// Create a ParameterUnit from a u32.
+    pub fn from_u32(u: u32) -> Option<ParameterUnit> {
+        match u {
+            0 => Some(ParameterUnit::Generic),
+            1 => Some(ParameterUnit::Indexed),
+            2 => Some(ParameterUnit::Boolean),
+            3 => Some(ParameterUnit::Percent),
+            4 => Some(ParameterUnit::Seconds),
+            5 => Some(ParameterUnit::SampleFrames),
+            6 => Some(ParameterUnit::Phase),
+            7 => Some(ParameterUnit::Rate),
+            8 => Some(ParameterUnit::Hertz),
+            9 => Some(ParameterUnit::Cents),
+            10 => Some(ParameterUnit::RelativeSemiTones),
+            11 => Some(ParameterUnit::MIDINoteNumber),
+            12 => Some(ParameterUnit::MIDIController),
+            13 => Some(ParameterUnit::Decibels),
+            14 => Some(ParameterUnit::LinearGain),
+            15 => Some(ParameterUnit::Degrees),
+            16 => Some(ParameterUnit::EqualPowerCrossfade),
+            17 => Some(ParameterUnit::MixerFaderCurve1),
+            18 => Some(ParameterUnit::Pan),
+            19 => Some(ParameterUnit::Meters),
+            20 => Some(ParameterUnit::AbsoluteCents),
+            21 => Some(ParameterUnit::Octaves),
+            22 => Some(ParameterUnit::BPM),
+            23 => Some(ParameterUnit::Beats),
+            24 => Some(ParameterUnit::Milliseconds),
+            25 => Some(ParameterUnit::Ratio),
+            26 => Some(ParameterUnit::CustomUnit),
+            _ => None,
+        }
+    }
+}
+
+
+bitflags! {
+    /// Flags describing a parameter.
+    pub struct ParameterFlags: u32 {
+        /// The `cf_name` of the parameter must be released by the host.
+        const CF_NAME_RELEASE = 1 << 4;
+        /// The parameter should not be saved in presets.
+        const OMIT_FROM_PRESETS = 1 << 13;
+        /// The parameter's history should be plotted by the host.
+        const PLOT_HISTORY = 1 << 14;
+        /// The parameter is a read-only meter.
+        const METER_READ_ONLY = 1 << 15;
+        /// The bits that represent the parameter's display scale (e.g. square root or cubed).
+        const DISPLAY_MASK = (7 << 16) | (1 << 22);
+        /// The parameter belongs to a clump of related parameters.
+        const HAS_CLUMP = 1 << 20;
+        /// The parameter's values have string representations.
+        const VALUES_HAVE_STRINGS = 1 << 21;
+        /// The parameter should be displayed on a logarithmic scale.
+        const DISPLAY_LOGARITHMIC = 1 << 22;
+        /// The parameter has a high resolution.
+        const IS_HIGH_RESOLUTION = 1 << 23;
+        /// Changing the parameter may not be real-time safe.
+        const NON_REAL_TIME = 1 << 24;
+        /// The parameter may be ramped via `AudioUnitScheduleParameters`.
+        const CAN_RAMP = 1 << 25;
+        /// The parameter is only intended for expert users.
+        const EXPERT_MODE = 1 << 26;
+        /// The parameter's name is provided as a `CFString`.
+        const HAS_CF_NAME_STRING = 1 << 27;
+        /// Changing the parameter may change the values of parameters in any scope.
+        const IS_GLOBAL_META = 1 << 28;
+        /// Changing the parameter may change the values of parameters in the same element.
+        const IS_ELEMENT_META = 1 << 29;
+        /// The parameter may be read.
+        const IS_READABLE = 1 << 30;
+        /// The parameter may be written.
+        const IS_WRITABLE = 1 << 31;
+    }
+}
+
+
+/// A description of a parameter, as returned by the `ParameterInfo` property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterInfo {
+    /// The ID of the parameter.
+    pub id: u32,
+    /// The name of the parameter.
+    pub name: String,
+    /// The unit in which the parameter is expressed, if it is a known unit.
+    pub unit: Option<ParameterUnit>,
+    /// The minimum value of the parameter.
+    pub min: f32,
+    /// The maximum value of the parameter.
+    pub max: f32,
+    /// The default value of the parameter.
+    pub default: f32,
+    /// Flags describing the parameter.
+    pub flags: ParameterFlags,
+}
+
+impl ParameterInfo {
+
+    /// Convert from the `sys::AudioUnitParameterInfo` of the parameter with the given ID.
+    ///
+    /// The name is taken from the `cfNameString` if the `HAS_CF_NAME_STRING` flag is set, or
+    /// otherwise from the `name` array, which is not necessarily nul-terminated.
+    ///
+    /// This is unsafe, as the `cfNameString` must be valid if the `HAS_CF_NAME_STRING` flag is
+    /// set, and is released if the `CF_NAME_RELEASE` flag is set.
+    pub unsafe fn from_sys(id: u32, info: &sys::AudioUnitParameterInfo) -> Self {
+        let flags = ParameterFlags::from_bits_truncate(info.flags);
+        let name = if flags.contains(ParameterFlags::HAS_CF_NAME_STRING) {
+            let name = info.cfNameString;
+            if flags.contains(ParameterFlags::CF_NAME_RELEASE) {
+                cf::to_string_and_release(name)
+            } else {
+                cf::to_string(name)
+            }
+        } else {
+            None
+        };
+        let name = name.unwrap_or_else(|| {
+            // The name fills the whole array if it is not nul-terminated.
+            let len = info.name.iter().position(|&c| c == 0).unwrap_or(info.name.len());
+            let bytes: Vec<u8> = info.name[..len].iter().map(|&c| c as u8).collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        });
+
+        ParameterInfo {
+            id: id,
+            name: name,
+            unit: ParameterUnit::from_u32(info.unit as u32),
+            min: info.minValue,
+            max: info.maxValue,
+            default: info.defaultValue,
+            flags: flags,
+        }
+    }
+
+}
+
+
+/// A change to a parameter's value that is scheduled at a frame offset within the next render
+/// cycle.
+///
+/// Scheduled via [**AudioUnit::schedule_parameters**](../struct.AudioUnit#method.schedule_parameters)
+/// or a [**ParameterScheduler**](./struct.ParameterScheduler).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ParameterEvent {
+    /// The ID of the parameter.
+    pub param: u32,
+    /// The scope of the parameter.
+    pub scope: Scope,
+    /// The element of the parameter.
+    pub element: Element,
+    /// The offset in frames from the start of the next render cycle at which the event begins.
+    pub offset_frames: u32,
+    /// Whether the value changes immediately or is ramped.
+    pub kind: ParameterEventKind,
+}
+
+/// The way in which a **ParameterEvent** changes the value of a parameter.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParameterEventKind {
+    /// The parameter is set to the given value at the event's offset.
+    Immediate(f32),
+    /// The parameter is linearly ramped from `start` to `end` over `duration` frames, beginning
+    /// at the event's offset.
+    ///
+    /// Only parameters with the `CAN_RAMP` flag support ramping.
+    Ramp {
+        duration: u32,
+        start: f32,
+        end: f32,
+    },
+}
+
+/// A handle for scheduling parameter events on an **AudioUnit** from another thread, e.g. from
+/// within a render callback.
+///
+/// Returned by [**AudioUnit::parameter_scheduler**](../struct.AudioUnit#method.parameter_scheduler).
+///
+/// Scheduling neither allocates nor blocks, so it is safe to do so on the render thread. The
+/// handle may outlive the **AudioUnit**, in which case scheduling returns an error.
+#[derive(Clone)]
+pub struct ParameterScheduler {
+    instance: sys::AudioUnit,
+    // Write-locked by the `AudioUnit` while it is disposed of, so that any number of schedulers
+    // and listeners may read it concurrently.
+    is_alive: Arc<RwLock<bool>>,
+}
+
+
+impl<S> AudioUnit<S> {
+
+    /// Gets the current value of a parameter.
+    ///
+    /// The parameter may be given either as a raw `u32` ID or as one of the typed parameter enums
+    /// such as `DelayParam::WetDryMix`, which are usually found on the `Global` scope of the
+    /// `Output` element.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn get_parameter<P>(&self, param: P, scope: Scope, elem: Element) -> Result<f32, Error>
+        where P: Parameter,
+    {
+        let mut value: sys::AudioUnitParameterValue = 0.0;
+        unsafe {
+            try!(Error::from_os_status(sys::AudioUnitGetParameter(
+                self.instance,
+                param.id(),
+                scope as c_uint,
+                elem.to_u32(),
+                &mut value,
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Sets the value of a parameter.
+    ///
+    /// The change takes effect at the start of the next render cycle. To schedule changes at
+    /// specific frames within a render cycle, see `schedule_parameters`.
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn set_parameter<P>(&mut self, param: P, scope: Scope, elem: Element, value: f32)
+        -> Result<(), Error>
+        where P: Parameter,
+    {
+        unsafe {
+            try!(Error::from_os_status(sys::AudioUnitSetParameter(
+                self.instance,
+                param.id(),
+                scope as c_uint,
+                elem.to_u32(),
+                value,
+                0,
+            )));
+        }
+        Ok(())
+    }
+
+    /// The IDs of all parameters within the given scope.
+    pub fn parameter_list(&self, scope: Scope) -> Result<Vec<u32>, Error> {
+        let id = sys::kAudioUnitProperty_ParameterList;
+        match self.get_property_vec(id, scope, Element::Output) {
+            // Units without any parameters in the scope may not support the property at all.
+            Err(Error::AudioUnit(AudioUnitError::InvalidProperty)) => Ok(vec![]),
+            result => result,
+        }
+    }
+
+    /// A description of the given parameter within the given scope, including its name, unit
+    /// and range.
+    pub fn parameter_info<P>(&self, param: P, scope: Scope) -> Result<ParameterInfo, Error>
+        where P: Parameter,
+    {
+        let id = param.id();
+        // The `ParameterInfo` property is queried with the parameter ID as its element.
+        let info_id = sys::kAudioUnitProperty_ParameterInfo;
+        let info: sys::AudioUnitParameterInfo =
+            try!(self.get_property(info_id, scope, Element(id)));
+        Ok(unsafe { ParameterInfo::from_sys(id, &info) })
+    }
+
+    /// A description of every parameter within the given scope.
+    ///
+    /// Returns the first error that occurs while describing a parameter, rather than skipping
+    /// the parameters that can't be described. Use `parameter_list` and `parameter_info` to
+    /// handle each parameter's error individually.
+    pub fn parameters(&self, scope: Scope) -> Result<Vec<ParameterInfo>, Error> {
+        let ids = try!(self.parameter_list(scope));
+        ids.into_iter().map(|id| self.parameter_info(id, scope)).collect()
+    }
+
+    /// Schedules the given parameter events to be applied during the next render cycle.
+    ///
+    /// Unlike `set_parameter`, this allows for changes at sample-accurate offsets within the
+    /// cycle as well as for linear ramps, avoiding the "zipper" noise caused by changing values
+    /// once per block. To schedule events from within a render callback, see
+    /// [**parameter_scheduler**](./struct.AudioUnit#method.parameter_scheduler).
+    ///
+    /// **Available** in OS X v10.2 and later.
+    pub fn schedule_parameters(&mut self, events: &[ParameterEvent]) -> Result<(), Error> {
+        unsafe { schedule_parameters(self.instance, events) }
+    }
+
+    /// A handle that may be moved into a render callback (or any other thread) in order to
+    /// schedule parameter events on this audio unit.
+    pub fn parameter_scheduler(&self) -> ParameterScheduler {
+        ParameterScheduler {
+            instance: self.instance,
+            is_alive: self.is_alive.clone(),
+        }
+    }
+
+}
+
+
+
+impl ParameterEvent {
+
+    /// An event that sets the given parameter on the `Global` scope to `value` at the given
+    /// frame offset.
+    pub fn immediate<P>(param: P, offset_frames: u32, value: f32) -> Self
+        where P: Parameter,
+    {
+        ParameterEvent {
+            param: param.id(),
+            scope: Scope::Global,
+            element: Element::Output,
+            offset_frames: offset_frames,
+            kind: ParameterEventKind::Immediate(value),
+        }
+    }
+
+    /// An event that linearly ramps the given parameter on the `Global` scope from `start` to
+    /// `end` over `duration` frames, beginning at the given frame offset.
+    pub fn ramp<P>(param: P, offset_frames: u32, duration: u32, start: f32, end: f32) -> Self
+        where P: Parameter,
+    {
+        ParameterEvent {
+            param: param.id(),
+            scope: Scope::Global,
+            element: Element::Output,
+            offset_frames: offset_frames,
+            kind: ParameterEventKind::Ramp { duration: duration, start: start, end: end },
+        }
+    }
+
+    /// Apply the event to the parameter on the given scope and element rather than `Global`.
+    pub fn scoped(self, scope: Scope, element: Element) -> Self {
+        ParameterEvent { scope: scope, element: element, ..self }
+    }
+
+    /// Convert to the `sys::AudioUnitParameterEvent` expected by `AudioUnitScheduleParameters`.
+    pub fn to_sys(&self) -> sys::AudioUnitParameterEvent {
+        let (event_type, event_values) = match self.kind {
+            ParameterEventKind::Immediate(value) => {
+                let immediate = sys::AudioUnitParameterEvent__bindgen_ty_1__bindgen_ty_2 {
+                    bufferOffset: self.offset_frames,
+                    value: value,
+                };
+                let values = sys::AudioUnitParameterEvent__bindgen_ty_1 { immediate: immediate };
+                (sys::kParameterEvent_Immediate, values)
+            },
+            ParameterEventKind::Ramp { duration, start, end } => {
+                let ramp = sys::AudioUnitParameterEvent__bindgen_ty_1__bindgen_ty_1 {
+                    startBufferOffset: self.offset_frames as i32,
+                    durationInFrames: duration,
+                    startValue: start,
+                    endValue: end,
+                };
+                let values = sys::AudioUnitParameterEvent__bindgen_ty_1 { ramp: ramp };
+                (sys::kParameterEvent_Ramped, values)
+            },
+        };
+        sys::AudioUnitParameterEvent {
+            scope: self.scope as c_uint,
+            element: self.element.to_u32(),
+            parameter: self.param,
+            eventType: event_type,
+            eventValues: event_values,
+        }
+    }
+
+}
+
+
+impl ParameterScheduler {
+
+    /// Schedules the given parameter events to be applied during the next render cycle.
+    ///
+    /// Returns `Err(Error::AudioUnit(AudioUnitError::Uninitialized))` if the **AudioUnit** has
+    /// been disposed of, or is currently being disposed of.
+    pub fn schedule(&self, events: &[ParameterEvent]) -> Result<(), Error> {
+        // Never block here, as we may be on the render thread.
+        let is_alive = match self.is_alive.try_read() {
+            Ok(is_alive) => is_alive,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                return Err(Error::AudioUnit(AudioUnitError::Uninitialized));
+            },
+        };
+        if !*is_alive {
+            return Err(Error::AudioUnit(AudioUnitError::Uninitialized));
+        }
+        unsafe { schedule_parameters(self.instance, events) }
+    }
+
+}
+
+
+unsafe impl Send for ParameterScheduler {}
+
+
+/// Schedules the given events in fixed-size chunks so that no allocation is required.
+unsafe fn schedule_parameters(instance: sys::AudioUnit, events: &[ParameterEvent])
+    -> Result<(), Error>
+{
+    const CHUNK_LEN: usize = 32;
+    let mut sys_events: [sys::AudioUnitParameterEvent; CHUNK_LEN] = mem::zeroed();
+    for chunk in events.chunks(CHUNK_LEN) {
+        for (sys_event, event) in sys_events.iter_mut().zip(chunk) {
+            *sys_event = event.to_sys();
+        }
+        try!(Error::from_os_status(sys::AudioUnitScheduleParameters(
+            instance,
+            sys_events.as_ptr(),
+            chunk.len() as u32,
+        )));
+    }
+    Ok(())
+}
+
+macro_rules! impl_parameter {
+    ($($T:ident)*) => {
+        $(
+            impl Parameter for $T {
+                fn id(&self) -> u32 {
+                    *self as u32
+                }
+            }
+        )*
+    };
+}
+
+impl_parameter! {
+    PeakLimiterParam
+    DynamicsProcessorParam
+    LowPassParam
+    HighPassParam
+    BandPassParam
+    HighShelfParam
+    LowShelfParam
+    ParametricEQParam
+    DistortionParam
+    DelayParam
+    SampleDelayParam
+    MultiBandCompressorParam
+    MatrixReverbParam
+    PitchParam
+    NewTimePitchParam
+    AUFilterParam
+    NetSendParam
+    RogerBeepParam
+}
+
+
+/// Parameters of the `EffectType::PeakLimiter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PeakLimiterParam {
+    /// Global, seconds, 0.001 -> 0.03, 0.012.
+    AttackTime = 0,
+    /// Global, seconds, 0.001 -> 0.06, 0.024.
+    DecayTime = 1,
+    /// Global, decibels, -40 -> 40, 0.
+    PreGain = 2,
+}
+
+/// Parameters of the `EffectType::DynamicsProcessor` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DynamicsProcessorParam {
+    /// Global, decibels, -40 -> 20, -20.
+    Threshold = 0,
+    /// Global, decibels, 0.1 -> 40.0, 5.
+    HeadRoom = 1,
+    /// Global, rate, 1 -> 50.0, 2.
+    ExpansionRatio = 2,
+    /// Global, decibels.
+    ExpansionThreshold = 3,
+    /// Global, seconds, 0.0001 -> 0.2, 0.001.
+    AttackTime = 4,
+    /// Global, seconds, 0.01 -> 3, 0.05.
+    ReleaseTime = 5,
+    /// Global, decibels, -40 -> 40, 0.
+    MasterGain = 6,
+    /// Global, decibels, read-only.
+    CompressionAmount = 1000,
+    /// Global, decibels, read-only.
+    InputAmplitude = 2000,
+    /// Global, decibels, read-only.
+    OutputAmplitude = 3000,
+}
+
+/// Parameters of the `EffectType::LowPassFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LowPassParam {
+    /// Global, hertz, 10 -> (sample rate / 2), 6900.
+    CutoffFrequency = 0,
+    /// Global, decibels, -20 -> 40, 0.
+    Resonance = 1,
+}
+
+/// Parameters of the `EffectType::HighPassFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HighPassParam {
+    /// Global, hertz, 10 -> (sample rate / 2), 6900.
+    CutoffFrequency = 0,
+    /// Global, decibels, -20 -> 40, 0.
+    Resonance = 1,
+}
+
+/// Parameters of the `EffectType::BandPassFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BandPassParam {
+    /// Global, hertz, 20 -> (sample rate / 2), 5000.
+    CenterFrequency = 0,
+    /// Global, cents, 100 -> 12000, 600.
+    Bandwidth = 1,
+}
+
+/// Parameters of the `EffectType::HighShelfFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HighShelfParam {
+    /// Global, hertz, 10000 -> (sample rate / 2), 10000.
+    CutOffFrequency = 0,
+    /// Global, decibels, -40 -> 40, 0.
+    Gain = 1,
+}
+
+/// Parameters of the `EffectType::LowShelfFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LowShelfParam {
+    /// Global, hertz, 10 -> 200, 80.
+    CutoffFrequency = 0,
+    /// Global, decibels, -40 -> 40, 0.
+    Gain = 1,
+}
+
+/// Parameters of the `EffectType::ParametricEQ` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParametricEQParam {
+    /// Global, hertz, 20 -> (sample rate / 2), 2000.
+    CenterFreq = 0,
+    /// Global, hertz, 0.1 -> 20, 1.0.
+    Q = 1,
+    /// Global, decibels, -20 -> 20, 0.
+    Gain = 2,
+}
+
+/// Parameters of the `EffectType::Distortion` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DistortionParam {
+    /// Global, milliseconds, 0.1 -> 500, 0.1.
+    Delay = 0,
+    /// Global, rate, 0.1 -> 50, 1.0.
+    Decay = 1,
+    /// Global, percent, 0 -> 100, 50.
+    DelayMix = 2,
+    /// Global, percent, 0 -> 100, 50.
+    Decimation = 3,
+    /// Global, percent, 0 -> 100, 0.
+    Rounding = 4,
+    /// Global, percent, 0 -> 100, 50.
+    DecimationMix = 5,
+    /// Global, linear gain, 0 -> 1, 1.
+    LinearTerm = 6,
+    /// Global, linear gain, 0 -> 20, 0.
+    SquaredTerm = 7,
+    /// Global, linear gain, 0 -> 20, 0.
+    CubicTerm = 8,
+    /// Global, percent, 0 -> 100, 50.
+    PolynomialMix = 9,
+    /// Global, hertz, 0.5 -> 8000, 100.
+    RingModFreq1 = 10,
+    /// Global, hertz, 0.5 -> 8000, 100.
+    RingModFreq2 = 11,
+    /// Global, percent, 0 -> 100, 50.
+    RingModBalance = 12,
+    /// Global, percent, 0 -> 100, 0.
+    RingModMix = 13,
+    /// Global, decibels, -80 -> 20, -6.
+    SoftClipGain = 14,
+    /// Global, percent, 0 -> 100, 50.
+    FinalMix = 15,
+}
+
+/// Parameters of the `EffectType::Delay` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DelayParam {
+    /// Global, equal power crossfade, 0 -> 100, 50.
+    WetDryMix = 0,
+    /// Global, seconds, 0 -> 2, 1.
+    DelayTime = 1,
+    /// Global, percent, -100 -> 100, 50.
+    Feedback = 2,
+    /// Global, hertz, 10 -> (sample rate / 2), 15000.
+    LopassCutoff = 3,
+}
+
+/// Parameters of the `EffectType::SampleDelay` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SampleDelayParam {
+    /// Global, sample frames, 0 -> 512, 0.
+    DelayFrames = 0,
+}
+
+/// Parameters of the `EffectType::MultiBandCompressor` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MultiBandCompressorParam {
+    /// Global, decibels, -40 -> 40, 0.
+    Pregain = 0,
+    /// Global, decibels, -40 -> 40, 0.
+    Postgain = 1,
+    /// Global, hertz, 20 -> (sample rate / 2), 120.
+    Crossover1 = 2,
+    /// Global, hertz, 20 -> (sample rate / 2), 700.
+    Crossover2 = 3,
+    /// Global, hertz, 20 -> (sample rate / 2), 3000.
+    Crossover3 = 4,
+    /// Global, decibels, -100 -> 0, -22.
+    Threshold1 = 5,
+    /// Global, decibels, -100 -> 0, -32.
+    Threshold2 = 6,
+    /// Global, decibels, -100 -> 0, -33.
+    Threshold3 = 7,
+    /// Global, decibels, -100 -> 0, -36.
+    Threshold4 = 8,
+    /// Global, decibels, 0.1 -> 30, 5.
+    Headroom1 = 9,
+    /// Global, decibels, 0.1 -> 30, 12.
+    Headroom2 = 10,
+    /// Global, decibels, 0.1 -> 30, 5.
+    Headroom3 = 11,
+    /// Global, decibels, 0.1 -> 30, 7.5.
+    Headroom4 = 12,
+    /// Global, seconds, 0.001 -> 0.200, 0.080.
+    AttackTime = 13,
+    /// Global, seconds, 0.010 -> 3, 0.120.
+    ReleaseTime = 14,
+    /// Global, decibels, -20 -> 20, 0.
+    EQ1 = 15,
+    /// Global, decibels, -20 -> 20, 0.
+    EQ2 = 16,
+    /// Global, decibels, -20 -> 20, 0.
+    EQ3 = 17,
+    /// Global, decibels, -20 -> 20, 0.
+    EQ4 = 18,
+}
+
+/// Parameters of the `EffectType::MatrixReverb` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MatrixReverbParam {
+    /// Global, equal power crossfade, 0 -> 100, 100.
+    DryWetMix = 0,
+    /// Global, equal power crossfade, 0 -> 100, 50.
+    SmallLargeMix = 1,
+    /// Global, seconds, 0.005 -> 0.020, 0.06.
+    SmallSize = 2,
+    /// Global, seconds, 0.4 -> 10.0, 3.07.
+    LargeSize = 3,
+    /// Global, seconds, 0.001 -> 0.03, 0.025.
+    PreDelay = 4,
+    /// Global, seconds, 0.001 -> 0.1, 0.035.
+    LargeDelay = 5,
+    /// Global, generic, 0 -> 1, 0.28.
+    SmallDensity = 6,
+    /// Global, generic, 0 -> 1, 0.82.
+    LargeDensity = 7,
+    /// Global, generic, 0 -> 1, 0.3.
+    LargeDelayRange = 8,
+    /// Global, generic, 0.1 -> 1, 0.96.
+    SmallBrightness = 9,
+    /// Global, generic, 0.1 -> 1, 0.49.
+    LargeBrightness = 10,
+    /// Global, generic, 0 -> 1, 0.5.
+    SmallDelayRange = 11,
+    /// Global, hertz, 0.001 -> 2.0, 1.0.
+    ModulationRate = 12,
+    /// Global, generic, 0.0 -> 1.0, 0.2.
+    ModulationDepth = 13,
+    /// Global, hertz, 10 -> (sample rate / 2), 800.
+    FilterFrequency = 14,
+    /// Global, octaves, 0.05 -> 4.0, 3.0.
+    FilterBandwidth = 15,
+    /// Global, decibels, -18 -> 18, 0.
+    FilterGain = 16,
+}
+
+/// Parameters of the `EffectType::Pitch` and `EffectType::TimePitch` units.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PitchParam {
+    /// Global, rate, 1/32 -> 32.0, 1.0.
+    Rate = 0,
+    /// Global, cents, -2400 -> 2400, 0.0.
+    Pitch = 1,
+    /// Global, generic, 0.0 -> 1.0, 0.0.
+    EffectBlend = 2,
+}
+
+/// Parameters of the `EffectType::NewTimePitch` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NewTimePitchParam {
+    /// Global, rate, 1/32 -> 32.0, 1.0.
+    Rate = 0,
+    /// Global, cents, -2400 -> 2400, 0.0.
+    Pitch = 1,
+    /// Global, generic, 3.0 -> 32.0, 8.0.
+    Overlap = 4,
+    /// Global, boolean, 0 -> 1, 1.
+    EnablePeakLocking = 6,
+}
+
+/// Parameters of the `EffectType::AUFilter` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AUFilterParam {
+    /// Global, indexed, the type of the low frequency filter.
+    LowFilterType = 0,
+    /// Global, hertz, 10 -> (sample rate / 2), 100.
+    LowFrequency = 1,
+    /// Global, decibels, -18 -> 18, 0.
+    LowGain = 2,
+    /// Global, hertz, 10 -> (sample rate / 2), 100.
+    CenterFreq1 = 3,
+    /// Global, decibels, -18 -> 18, 0.
+    CenterGain1 = 4,
+    /// Global, octaves, 0.05 -> 3, 2.0.
+    Bandwidth1 = 5,
+    /// Global, hertz, 10 -> (sample rate / 2), 100.
+    CenterFreq2 = 6,
+    /// Global, decibels, -18 -> 18, 0.
+    CenterGain2 = 7,
+    /// Global, octaves, 0.05 -> 3, 2.0.
+    Bandwidth2 = 8,
+    /// Global, hertz, 10 -> (sample rate / 2), 100.
+    CenterFreq3 = 9,
+    /// Global, decibels, -18 -> 18, 0.
+    CenterGain3 = 10,
+    /// Global, octaves, 0.05 -> 3, 2.0.
+    Bandwidth3 = 11,
+    /// Global, indexed, the type of the high frequency filter.
+    HighFilterType = 12,
+    /// Global, hertz, 10 -> (sample rate / 2), 100.
+    HighFrequency = 13,
+    /// Global, decibels, -18 -> 18, 0.
+    HighGain = 14,
+}
+
+/// Parameters of the `EffectType::NetSend` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NetSendParam {
+    /// Global, indexed, read-only.
+    Status = 0,
+    /// Global, generic, read-only.
+    NumClients = 1,
+}
+
+/// Parameters of the `EffectType::RogerBeep` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RogerBeepParam {
+    /// Global, decibels, -80 -> 0, -75.
+    InGateThreshold = 0,
+    /// Global, milliseconds, 0 -> 1000, 125.
+    InGateThresholdTime = 1,
+    /// Global, decibels, -80 -> 0, -100.
+    OutGateThreshold = 2,
+    /// Global, milliseconds, 0 -> 1000, 1000.
+    OutGateThresholdTime = 3,
+    /// Global, indexed, 0 -> 2, 2.
+    Sensitivity = 4,
+    /// Global, indexed.
+    RogerType = 5,
+    /// Global, decibels, -80 -> 20, -6.
+    RogerGain = 6,
+}
+
+/// Parameters of the `EffectType::GraphicEQ` unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GraphicEQParam {
+    /// Global, decibels, -24 -> 24, 0. The gain of the band at the given index.
+    Band(u32),
+    /// Global, indexed, `0` for 10 bands or `1` for 31 bands.
+    NumberOfBands,
+}
+
+impl Parameter for GraphicEQParam {
+    fn id(&self) -> u32 {
+        match *self {
+            GraphicEQParam::Band(band) => band,
+            GraphicEQParam::NumberOfBands => 10000,
+        }
+    }
+}
+
+/// Parameters of the `EffectType::NBandEQ` unit.
+///
+/// Each of the per-band parameters take the index of the band.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NBandEQParam {
+    /// Global, decibels, -96 -> 24, 0.
+    GlobalGain,
+    /// Global, boolean, whether or not the band is bypassed.
+    BypassBand(u32),
+    /// Global, indexed, the type of filter used by the band.
+    FilterType(u32),
+    /// Global, hertz, 20 -> (sample rate / 2).
+    Frequency(u32),
+    /// Global, decibels, -96 -> 24.
+    Gain(u32),
+    /// Global, octaves, 0.05 -> 5.0.
+    Bandwidth(u32),
+}
+
+impl Parameter for NBandEQParam {
+    fn id(&self) -> u32 {
+        match *self {
+            NBandEQParam::GlobalGain => 0,
+            NBandEQParam::BypassBand(band) => 1000 + band,
+            NBandEQParam::FilterType(band) => 2000 + band,
+            NBandEQParam::Frequency(band) => 3000 + band,
+            NBandEQParam::Gain(band) => 4000 + band,
+            NBandEQParam::Bandwidth(band) => 5000 + band,
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Strongly typed **AudioUnit** properties.
+//!
+//! Each of the types within this module represents one of the standard `kAudioUnitProperty_*` or
+//! `kAudioOutputUnitProperty_*` identifiers, along with the type of its value and the scope and
+//! element that it is most commonly accessed on. They may be used with the
+//! [**AudioUnit::get**](../struct.AudioUnit#method.get) and
+//! [**AudioUnit::set**](../struct.AudioUnit#method.set) methods.
+//!
+//! Properties that must be set before the **AudioUnit** is initialized, such as `EnableIO`, may
+//! only be set on an `AudioUnit<Uninitialized>` (see [**Settable**](./trait.Settable)).
+//!
+//! Original documentation [here](https://developer.apple.com/documentation/audiotoolbox/1534199-generic_audio_unit_properties).
+
+use super::{Element, Scope, Uninitialized};
+use sys;
+
+
+/// A plain old data type into which an **AudioUnit** may write a property's value directly.
+///
+/// This is unsafe to implement, as the bytes written by the **AudioUnit** are trusted to be a
+/// valid value of the type. Implementors must be `#[repr(C)]` or primitive types for which every
+/// bit pattern (including all zeroes) is a valid value, e.g. not `bool`, `char`, enums or
+/// references.
+///
+/// ```compile_fail
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, Element, IOType, Scope};
+/// # use coreaudio::sys;
+/// # fn main() {
+/// let audio_unit = AudioUnit::new(IOType::DefaultOutput).unwrap();
+/// // Only `0` and `1` are valid `bool`s, so a `bool` may not be read from a property.
+/// let id = sys::kAudioOutputUnitProperty_IsRunning;
+/// let running: bool = audio_unit.get_property(id, Scope::Global, Element::Output).unwrap();
+/// # }
+/// ```
+pub unsafe trait Pod: Copy {}
+
+macro_rules! impl_pod {
+    ($($T:ty),* $(,)*) => {
+        $(
+            unsafe impl Pod for $T {}
+        )*
+    };
+}
+
+impl_pod! {
+    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64,
+    sys::AudioStreamBasicDescription,
+    sys::AURenderCallbackStruct,
+    sys::AUChannelInfo,
+    sys::AudioUnitParameterInfo,
+    sys::AudioComponentDescription,
+    sys::AudioTimeStamp,
+}
+
+unsafe impl<T> Pod for *const T {}
+unsafe impl<T> Pod for *mut T {}
+
+
+/// An **AudioUnit** property with a fixed-size value.
+pub trait Property {
+    /// The type of the property's value.
+    type Value: Pod;
+    /// The `AudioUnitPropertyID` of the property.
+    const ID: u32;
+    /// The scope on which the property is usually accessed.
+    const SCOPE: Scope;
+    /// The element on which the property is usually accessed.
+    const ELEMENT: Element;
+}
+
+/// A **Property** that may be set on an **AudioUnit** in the state `S`.
+///
+/// Properties that must be set before `AudioUnitInitialize` are only settable on an
+/// `AudioUnit<Uninitialized>`, while all other properties are settable in either state.
+///
+/// ```compile_fail
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, IOType};
+/// # use coreaudio::audio_unit::property::EnableIO;
+/// # fn main() {
+/// let mut audio_unit = AudioUnit::new(IOType::HalOutput).unwrap();
+/// audio_unit.set(EnableIO, &1).unwrap();
+/// # }
+/// ```
+pub trait Settable<S>: Property {}
+
+
+// Properties followed by `Uninitialized` may only be set before the audio unit is initialized.
+macro_rules! properties {
+    ($(
+        $(#[$attr:meta])*
+        $Name:ident: $Value:ty = $id:ident, $scope:ident, $elem:ident $(, $State:ident)*;
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Copy, Clone, Debug, Default, PartialEq)]
+            pub struct $Name;
+
+            impl Property for $Name {
+                type Value = $Value;
+                const ID: u32 = sys::$id;
+                const SCOPE: Scope = Scope::$scope;
+                const ELEMENT: Element = Element::$elem;
+            }
+
+            impl_settable!($Name $(, $State)*);
+        )*
+    };
+}
+
+macro_rules! impl_settable {
+    ($Name:ident) => {
+        impl<S> Settable<S> for $Name {}
+    };
+    ($Name:ident, $State:ident) => {
+        impl Settable<$State> for $Name {}
+    };
+}
+
+
+properties! {
+    /// The sample rate of the audio unit, in hertz.
+    SampleRate: f64 = kAudioUnitProperty_SampleRate, Input, Output;
+    /// The stream format of the audio unit.
+    ///
+    /// See [**StreamFormat**](../stream_format/struct.StreamFormat) for a friendlier
+    /// representation of the value.
+    StreamFormat: sys::AudioStreamBasicDescription =
+        kAudioUnitProperty_StreamFormat, Output, Output;
+    /// The number of elements (aka buses) within a scope.
+    ///
+    /// This must be set before the audio unit is initialized.
+    ElementCount: u32 = kAudioUnitProperty_ElementCount, Input, Output, Uninitialized;
+    /// The processing latency of the audio unit, in seconds.
+    Latency: f64 = kAudioUnitProperty_Latency, Global, Output;
+    /// The tail time of the audio unit (e.g. the decay of a reverb), in seconds.
+    TailTime: f64 = kAudioUnitProperty_TailTime, Global, Output;
+    /// The maximum number of frames that the audio unit may be asked to render at once.
+    ///
+    /// This must be set before the audio unit is initialized.
+    MaximumFramesPerSlice: u32 =
+        kAudioUnitProperty_MaximumFramesPerSlice, Global, Output, Uninitialized;
+    /// Whether or not an effect unit is bypassed (`1`) or processing (`0`).
+    BypassEffect: u32 = kAudioUnitProperty_BypassEffect, Global, Output;
+    /// The error (if any) that occurred during the most recent render.
+    LastRenderError: sys::OSStatus = kAudioUnitProperty_LastRenderError, Global, Output;
+    /// The render callback that provides the audio unit's input.
+    SetRenderCallback: sys::AURenderCallbackStruct =
+        kAudioUnitProperty_SetRenderCallback, Input, Output;
+    /// The quality of the audio unit's rendering, from `0` (minimum) to `127` (maximum).
+    RenderQuality: u32 = kAudioUnitProperty_RenderQuality, Global, Output;
+    /// The fraction of the render thread's time spent rendering, from `0.0` to `1.0`.
+    CPULoad: f64 = kAudioUnitProperty_CPULoad, Global, Output;
+    /// Whether or not the audio unit processes its input in-place (`1`) or not (`0`).
+    InPlaceProcessing: u32 = kAudioUnitProperty_InPlaceProcessing, Global, Output;
+    /// Whether or not the audio unit should allocate its own output buffers (`1`) or not (`0`).
+    ///
+    /// This must be set before the audio unit is initialized.
+    ShouldAllocateBuffer: u32 =
+        kAudioUnitProperty_ShouldAllocateBuffer, Output, Output, Uninitialized;
+    /// Whether or not the audio unit is rendering offline (`1`) rather than in real-time (`0`).
+    OfflineRender: u32 = kAudioUnitProperty_OfflineRender, Global, Output;
+    /// The audio device used by an I/O unit.
+    CurrentDevice: u32 = kAudioOutputUnitProperty_CurrentDevice, Global, Output;
+    /// Whether or not an I/O unit is currently running (`1`) or not (`0`).
+    IsRunning: u32 = kAudioOutputUnitProperty_IsRunning, Global, Output;
+    /// Whether or not IO is enabled (`1`) or disabled (`0`) for an I/O unit.
+    ///
+    /// Input is enabled on the `Input` scope of the `Input` element, while output is enabled on
+    /// the `Output` scope of the `Output` element. This must be set before the audio unit is
+    /// initialized.
+    EnableIO: u32 = kAudioOutputUnitProperty_EnableIO, Input, Input, Uninitialized;
+    /// Whether or not an I/O unit has input on the `Input` element or output on the `Output`
+    /// element.
+    HasIO: u32 = kAudioOutputUnitProperty_HasIO, Input, Input;
+    /// The callback that is called when input is available from an I/O unit.
+    SetInputCallback: sys::AURenderCallbackStruct =
+        kAudioOutputUnitProperty_SetInputCallback, Global, Output;
+    /// Whether or not the time stamps of an I/O unit start at zero (`1`) or at the device's
+    /// current time (`0`).
+    StartTimestampsAtZero: u32 = kAudioOutputUnitProperty_StartTimestampsAtZero, Global, Output;
+}
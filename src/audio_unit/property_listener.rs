@@ -0,0 +1,174 @@
+//! Listening for changes to the value of an **AudioUnit** property.
+
+use error::Error;
+use std::os::raw::{c_uint, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::{AudioUnit, Element, Scope};
+use sys;
+
+
+/// A guard representing a closure that is called each time an **AudioUnit** property changes.
+///
+/// Returned by [**AudioUnit::add_property_listener**](../struct.AudioUnit#method.add_property_listener).
+///
+/// The listener is removed and its closure freed when the guard is dropped. The guard may safely
+/// outlive the **AudioUnit**.
+///
+/// If the closure is still being called on another thread at the moment that the listener is
+/// removed (or if removing the listener fails), the closure is instead freed once the
+/// **AudioUnit** has been disposed of.
+pub struct PropertyListener {
+    instance: sys::AudioUnit,
+    id: u32,
+    // Whether or not the `instance` is still alive. Read-locked while removing the listener so
+    // that the `AudioUnit` cannot be disposed of concurrently.
+    is_alive: Arc<RwLock<bool>>,
+    retired: RetiredListeners,
+    wrapper: *mut PropertyListenerFnWrapper,
+}
+
+/// The boxed property listener closure along with the scope and element to listen on.
+pub struct PropertyListenerFnWrapper {
+    // Locked while called, as Core Audio may call the listener from multiple threads.
+    callback: Mutex<Box<dyn FnMut() + Send>>,
+    scope: c_uint,
+    elem: c_uint,
+    // The number of calls to the listener that are in progress.
+    in_flight: AtomicUsize,
+}
+
+/// The closures of the listeners that were still being called when they were removed from an
+/// **AudioUnit**, which are freed once the **AudioUnit** has been disposed of.
+pub type RetiredListeners = Arc<Mutex<Vec<Box<PropertyListenerFnWrapper>>>>;
+
+
+impl<S> AudioUnit<S> {
+
+    /// Call the given closure each time the value of the given property changes on the given
+    /// scope and element.
+    ///
+    /// This is useful for reacting to changes made by the system, e.g. when the device behind a
+    /// `HalOutput` unit changes its sample rate or stream format, or when `IsRunning` or
+    /// `LastRenderError` changes.
+    ///
+    /// The closure may be called on any thread, so it must be `Send`. The listener is removed
+    /// when the returned **PropertyListener** is dropped.
+    ///
+    /// A call to the closure is skipped while the closure is already being called, e.g. when the
+    /// closure sets the property that it is listening to. A panic within the closure is caught
+    /// and ignored, as it must not unwind into Core Audio.
+    ///
+    /// ```compile_fail
+    /// # extern crate coreaudio;
+    /// # use coreaudio::audio_unit::{AudioUnit, Element, IOType, Scope};
+    /// # use coreaudio::sys;
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # fn main() {
+    /// let mut audio_unit = AudioUnit::new(IOType::DefaultOutput).unwrap();
+    /// let changes = Rc::new(Cell::new(0));
+    /// let id = sys::kAudioUnitProperty_StreamFormat;
+    /// let listener = audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+    ///     changes.set(changes.get() + 1);
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// **Available** in OS X v10.0 and later.
+    pub fn add_property_listener<F>(&mut self, id: u32, scope: Scope, elem: Element, f: F)
+        -> Result<PropertyListener, Error>
+        where F: FnMut() + Send + 'static,
+    {
+        let wrapper = Box::new(PropertyListenerFnWrapper {
+            callback: Mutex::new(Box::new(f)),
+            scope: scope as c_uint,
+            elem: elem.to_u32(),
+            in_flight: AtomicUsize::new(0),
+        });
+
+        // Relinquish ownership of the closure so that it can be used as the listener's user data.
+        // We store the *mut so that we can convert back to a Box<PropertyListenerFnWrapper>
+        // within the `PropertyListener`'s Drop implementation (otherwise it would leak).
+        let wrapper_ptr = Box::into_raw(wrapper);
+        let status = unsafe {
+            sys::AudioUnitAddPropertyListener(
+                self.instance,
+                id,
+                Some(property_listener_proc),
+                wrapper_ptr as *mut c_void,
+            )
+        };
+        if let Err(err) = Error::from_os_status(status) {
+            unsafe { drop(Box::from_raw(wrapper_ptr)); }
+            return Err(err);
+        }
+
+        Ok(PropertyListener {
+            instance: self.instance,
+            id: id,
+            is_alive: self.is_alive.clone(),
+            retired: self.retired_listeners.clone(),
+            wrapper: wrapper_ptr,
+        })
+    }
+
+}
+
+
+unsafe impl Send for PropertyListener {}
+
+
+impl Drop for PropertyListener {
+    fn drop(&mut self) {
+        // Here, we transfer ownership of the closure back to the current scope.
+        let wrapper = unsafe { Box::from_raw(self.wrapper) };
+        let is_alive = self.is_alive.read().unwrap_or_else(|err| err.into_inner());
+        // If the audio unit has already been disposed of, the listener can no longer be called
+        // and there is nothing to remove, so the closure is dropped and cleaned up.
+        if *is_alive {
+            // We don't want to panic in `drop`, so errors are handled by keeping the closure.
+            let status = unsafe {
+                sys::AudioUnitRemovePropertyListenerWithUserData(
+                    self.instance,
+                    self.id,
+                    Some(property_listener_proc),
+                    self.wrapper as *mut c_void,
+                )
+            };
+            // Once removed, the listener is no longer called, but a call may still be in
+            // progress, either on another thread or on this one if the guard is dropped from
+            // within a listener. In that case, or if the listener could not be removed, the
+            // closure is kept until the audio unit is disposed of. Otherwise it is dropped here.
+            let in_flight = wrapper.in_flight.load(Ordering::SeqCst);
+            if Error::from_os_status(status).is_err() || in_flight != 0 {
+                let mut retired = self.retired.lock().unwrap_or_else(|err| err.into_inner());
+                retired.push(wrapper);
+            }
+        }
+    }
+}
+
+
+/// Callback procedure that will be called each time a listened-to property changes.
+extern "C" fn property_listener_proc(in_ref_con: *mut c_void,
+                                     _in_unit: sys::AudioUnit,
+                                     _in_id: sys::AudioUnitPropertyID,
+                                     in_scope: sys::AudioUnitScope,
+                                     in_element: sys::AudioUnitElement)
+{
+    let wrapper = unsafe { &*(in_ref_con as *const PropertyListenerFnWrapper) };
+    wrapper.in_flight.fetch_add(1, Ordering::SeqCst);
+    // The listener is called for every scope and element, so only call the closure for the
+    // requested ones.
+    if wrapper.scope == in_scope && wrapper.elem == in_element {
+        // The lock is already held if the closure re-entered the listener by setting the property
+        // that it is listening to, in which case the call is skipped rather than deadlocking.
+        if let Ok(mut callback) = wrapper.callback.try_lock() {
+            // Panics must not unwind across the FFI boundary.
+            panic::catch_unwind(AssertUnwindSafe(|| (*callback)())).ok();
+        }
+    }
+    wrapper.in_flight.fetch_sub(1, Ordering::SeqCst);
+}
@@ -1,8 +1,15 @@
-use error::{self, Error};
+use error::{AudioUnitError, Error};
+use std::any::Any;
+use std::cmp;
 use std::mem;
 use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
 use std::slice;
-use super::{AudioUnit, Element, Scope};
+use std::sync::{Arc, Mutex, TryLockError};
+use std::sync::atomic::{AtomicI32, Ordering};
+use super::{AudioBufferListBuf, AudioUnit, Element, SampleFormat, Scope, StreamFormat};
+use super::property;
 use sys;
 
 pub use self::action_flags::ActionFlags;
@@ -23,16 +30,63 @@ pub type InputProcFn = FnMut(*mut sys::AudioUnitRenderActionFlags,
 /// This type allows us to safely wrap a boxed `RenderCallback` to use within the input proc.
 pub struct InputProcFnWrapper {
     callback: Box<InputProcFn>,
+    // Whether or not the callback has panicked, in which case it is never called again.
+    poisoned: bool,
+    // Where the payload of a panic is stored for the owning `AudioUnit` to retrieve.
+    panic_payload: PanicPayload,
+    // The byte with which the buffers are filled in order to silence a poisoned callback.
+    silence_byte: u8,
 }
 
+/// The payload of the first panic to occur within one of an **AudioUnit**'s callbacks.
+pub type PanicPayload = Arc<Mutex<Option<Box<dyn Any + Send>>>>;
+
 /// Arguments given to the render callback function.
+///
+/// The arguments only borrow the audio unit's buffers for the duration of a single call to the
+/// callback, so neither the arguments nor the buffers may escape it:
+///
+/// ```compile_fail
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, Element, IOType};
+/// # use coreaudio::audio_unit::render_callback::Args;
+/// # use coreaudio::audio_unit::render_callback::data::NonInterleaved;
+/// # fn run() -> Result<(), coreaudio::Error> {
+/// let mut audio_unit = try!(AudioUnit::new(IOType::DefaultOutput));
+/// let mut escaped = Vec::new();
+/// try!(audio_unit.set_render_callback(Element::Output, move |args: Args<NonInterleaved<f32>>| {
+///     escaped.push(args.data);
+///     Ok(())
+/// }));
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap(); }
+/// ```
+///
+/// ```compile_fail
+/// # extern crate coreaudio;
+/// # use coreaudio::audio_unit::{AudioUnit, Element, IOType};
+/// # use coreaudio::audio_unit::render_callback::Args;
+/// # use coreaudio::audio_unit::render_callback::data::NonInterleaved;
+/// # fn run() -> Result<(), coreaudio::Error> {
+/// let mut audio_unit = try!(AudioUnit::new(IOType::DefaultOutput));
+/// let mut escaped: Vec<&mut [f32]> = Vec::new();
+/// try!(audio_unit.set_render_callback(Element::Output, move |args: Args<NonInterleaved<f32>>| {
+///     escaped.extend(args.data.channels_mut());
+///     Ok(())
+/// }));
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap(); }
+/// ```
 #[derive(Debug)]
-pub struct Args<D> {
+pub struct Args<'a, D: 'a> {
     /// A type wrapping the the buffer that matches the expected audio format.
-    pub data: D,
+    pub data: &'a mut D,
     /// Timing information for the callback.
     pub time_stamp: sys::AudioTimeStamp,
-    /// TODO
+    /// The element (bus) for which the callback is called, e.g. the input bus of a mixer that is
+    /// being rendered.
     pub bus_number: u32,
     /// The number of frames in the buffer as `usize` for easier indexing.
     pub num_frames: usize,
@@ -42,24 +96,43 @@ pub struct Args<D> {
     ///
     /// For example: if there is no audio to process, we can insert the `OUTPUT_IS_SILENCE` flag to
     /// indicate to the audio unit that the buffer does not need to be processed.
-    pub flags: action_flags::Handle,
+    pub flags: &'a mut action_flags::Handle,
 }
 
 
 /// Format specific render callback data.
 pub mod data {
+    use error::Error;
+    use std::cmp;
+    use std::iter;
     use std::marker::PhantomData;
     use std::slice;
+    use super::action_flags::{ActionFlags, Handle};
+    use super::super::stream_format::Layout;
     use super::super::StreamFormat;
-    use super::super::Sample;
+    use super::super::{Sample, SampleFormat};
+    use super::super::sample_format::{self, Tpdf};
     use sys;
 
     /// Audio data wrappers specific to the `AudioUnit`'s `AudioFormat`.
-    pub trait Data {
+    ///
+    /// A `Data` value is constructed for each call to a callback from the pointers given to the
+    /// `input_proc`, which are only valid for the duration of that call. The callback receives it
+    /// as `&'a mut Self` within its `Args<'a, Self>`.
+    ///
+    /// The soundness of the callbacks relies on this borrow: implementations must never hand out
+    /// references to the buffers that outlive `&self`, such as a `&'static mut [S]` field or a
+    /// method returning one. Store raw pointers and only create slices borrowed from `self`, as
+    /// `Interleaved` and `NonInterleaved` do.
+    pub trait Data: Sized {
         /// Check whether or not the stream format matches this type of data.
         fn does_stream_format_match(&StreamFormat) -> bool;
         /// We must be able to construct Self from arguments given to the `input_proc`.
-        unsafe fn from_input_proc_args(num_frames: u32, io_data: *mut sys::AudioBufferList) -> Self;
+        ///
+        /// Returns an `Error` if the buffers given by the audio unit do not have the layout
+        /// expected by this type of data.
+        unsafe fn from_input_proc_args(num_frames: u32, io_data: *mut sys::AudioBufferList)
+            -> Result<Self, Error>;
     }
 
     /// A raw pointer to the audio data so that the user may handle it themselves.
@@ -72,60 +145,172 @@ pub mod data {
         fn does_stream_format_match(_: &StreamFormat) -> bool {
             true
         }
-        unsafe fn from_input_proc_args(_num_frames: u32, io_data: *mut sys::AudioBufferList) -> Self {
-            Raw { data: io_data }
-        }
-    }
-
-    // TODO: When testing with the `HalOutput` audio unit it seemed not to allow interleaved data.
-    // Even though the `IS_NON_INTERLEAVED` flag was not set, the audio unit continues to deliver
-    // the audio as non-interleaved samples anyway. Investigate this, as it might not even be
-    // possible to use this type with audio units!
-    //
-    // /// An interleaved linear PCM buffer with samples of type `S`.
-    // pub struct Interleaved<'a, S> {
-    //     pub buffer: &'a mut [S],
-    //     pub channels: usize,
-    // }
-
-    // // Implementation for an interleaved linear PCM audio format.
-    // impl<'a, S> Data for Interleaved<'a, S>
-    //     where S: Sample,
-    // {
-    //     fn does_stream_format_match(format: &StreamFormat) -> bool {
-    //         !format.flags.contains(linear_pcm_flags::IS_NON_INTERLEAVED) &&
-    //             S::sample_format().does_match_flags(format.flags)
-    //     }
-
-    //     #[allow(non_snake_case)]
-    //     unsafe fn from_input_proc_args(frames: u32, io_data: *mut sys::AudioBufferList) -> Self {
-    //         // We're expecting a single interleaved buffer which will be the first in the array.
-    //         let sys::AudioBuffer { mNumberChannels, mDataByteSize, mData } = (*io_data).mBuffers[0];
-
-    //         // Ensure that the size of the data matches the size of the sample format
-    //         // multiplied by the number of frames.
-    //         //
-    //         // TODO: Return an Err instead of `panic`ing.
-    //         let buffer_len = frames as usize * mNumberChannels as usize;
-    //         let expected_size = ::std::mem::size_of::<S>() * buffer_len;
-    //         assert!(mDataByteSize as usize == expected_size);
-
-    //         let buffer: &mut [S] = {
-    //             let buffer_ptr = mData as *mut S;
-    //             slice::from_raw_parts_mut(buffer_ptr, buffer_len)
-    //         };
-
-    //         Interleaved {
-    //             buffer: buffer,
-    //             channels: mNumberChannels as usize,
-    //         }
-    //     }
-    // }
+        unsafe fn from_input_proc_args(_num_frames: u32, io_data: *mut sys::AudioBufferList)
+            -> Result<Self, Error>
+        {
+            Ok(Raw { data: io_data })
+        }
+    }
+
+    /// An interleaved linear PCM buffer with samples of type `S`.
+    pub struct Interleaved<S> {
+        /// The audio buffer, with the samples for each frame laid out contiguously.
+        buffer: *mut S,
+        /// The number of samples in the buffer.
+        len: usize,
+        /// The number of channels (samples) in each frame.
+        channels: usize,
+    }
+
+    /// An iterator produced by an `Interleaved`, yielding a reference to each frame.
+    pub struct Frames<'a, S: 'a> {
+        chunks: slice::Chunks<'a, S>,
+    }
+
+    /// An iterator produced by an `Interleaved`, yielding a mutable reference to each frame.
+    pub struct FramesMut<'a, S: 'a> {
+        chunks: slice::ChunksMut<'a, S>,
+    }
+
+    unsafe impl<S> Send for Interleaved<S> where S: Send {}
+
+    impl<'a, S> Iterator for Frames<'a, S> {
+        type Item = &'a [S];
+        fn next(&mut self) -> Option<Self::Item> {
+            self.chunks.next()
+        }
+    }
+
+    impl<'a, S> Iterator for FramesMut<'a, S> {
+        type Item = &'a mut [S];
+        fn next(&mut self) -> Option<Self::Item> {
+            self.chunks.next()
+        }
+    }
+
+    impl<S> Interleaved<S> {
+
+        /// The audio buffer, with the samples for each frame laid out contiguously.
+        pub fn buffer(&self) -> &[S] {
+            unsafe { slice::from_raw_parts(self.buffer, self.len) }
+        }
+
+        /// The mutable audio buffer, with the samples for each frame laid out contiguously.
+        pub fn buffer_mut(&mut self) -> &mut [S] {
+            unsafe { slice::from_raw_parts_mut(self.buffer, self.len) }
+        }
+
+        /// The number of channels (samples) in each frame.
+        pub fn channels(&self) -> usize {
+            self.channels
+        }
+
+        /// The number of frames in the buffer.
+        pub fn num_frames(&self) -> usize {
+            self.len / self.channels
+        }
+
+        /// An iterator yielding a reference to each frame in the buffer.
+        ///
+        /// Each frame is a slice with a sample for each channel.
+        pub fn frames(&self) -> Frames<S> {
+            Frames { chunks: self.buffer().chunks(self.channels) }
+        }
+
+        /// An iterator yielding a mutable reference to each frame in the buffer.
+        ///
+        /// Each frame is a slice with a sample for each channel.
+        pub fn frames_mut(&mut self) -> FramesMut<S> {
+            let channels = self.channels;
+            FramesMut { chunks: self.buffer_mut().chunks_mut(channels) }
+        }
+
+        /// An iterator yielding a reference to every sample in the buffer in interleaved order.
+        pub fn samples(&self) -> slice::Iter<S> {
+            self.buffer().iter()
+        }
+
+        /// An iterator yielding a mutable reference to every sample in the buffer in interleaved
+        /// order.
+        pub fn samples_mut(&mut self) -> slice::IterMut<S> {
+            self.buffer_mut().iter_mut()
+        }
+
+        /// Convert the buffer to the given `SampleFormat`, writing the interleaved samples to
+        /// `dst` as native-endian bytes.
+        ///
+        /// If `dither` is `Some` and the conversion reduces the bit depth, TPDF dither is applied.
+        pub fn convert_into(&self,
+                            dst_format: SampleFormat,
+                            dst: &mut [u8],
+                            dither: Option<&mut Tpdf>)
+            where S: Sample,
+        {
+            sample_format::convert_slice_to_bytes(self.buffer(), dst_format, dst, dither);
+        }
+
+        /// Fill the buffer with the interleaved native-endian samples of the given `SampleFormat`
+        /// read from `src`, converting each to `S`.
+        pub fn convert_from(&mut self,
+                            src_format: SampleFormat,
+                            src: &[u8],
+                            dither: Option<&mut Tpdf>)
+            where S: Sample,
+        {
+            sample_format::convert_bytes_to_slice(src_format, src, self.buffer_mut(), dither);
+        }
+
+    }
+
+    // Implementation for an interleaved linear PCM audio format.
+    impl<S> Data for Interleaved<S>
+        where S: Sample,
+    {
+        fn does_stream_format_match(format: &StreamFormat) -> bool {
+            format.layout == Layout::Interleaved &&
+                format.unpacked_bytes_per_sample.is_none() &&
+                S::sample_format().does_match_flags(format.flags)
+        }
+
+        #[allow(non_snake_case)]
+        unsafe fn from_input_proc_args(frames: u32, io_data: *mut sys::AudioBufferList)
+            -> Result<Self, Error>
+        {
+            // We're expecting a single interleaved buffer which will be the first in the array.
+            if (*io_data).mNumberBuffers == 0 {
+                return Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount);
+            }
+            let sys::AudioBuffer { mNumberChannels, mDataByteSize, mData } = (*io_data).mBuffers[0];
+            if mNumberChannels == 0 {
+                return Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount);
+            }
+
+            // Ensure that the size of the data matches the size of the sample format
+            // multiplied by the number of frames.
+            let buffer_len = frames as usize * mNumberChannels as usize;
+            let expected_size = ::std::mem::size_of::<S>() * buffer_len;
+            if mDataByteSize as usize != expected_size {
+                return Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount);
+            }
+
+            Ok(Interleaved {
+                buffer: mData as *mut S,
+                len: buffer_len,
+                channels: mNumberChannels as usize,
+            })
+        }
+    }
 
     /// A wrapper around the pointer to the `mBuffers` array.
+    ///
+    /// Only matches stream formats with the `IS_NON_INTERLEAVED` flag set. Some audio units, such
+    /// as `HalOutput`, do not set the flag in their default stream format, so either set a
+    /// non-interleaved stream format first or use `Interleaved`.
     pub struct NonInterleaved<S> {
         /// The list of audio buffers.
-        buffers: &'static mut [sys::AudioBuffer],
+        buffers: *mut sys::AudioBuffer,
+        /// The number of audio buffers.
+        num_buffers: usize,
         /// The number of frames in each channel.
         frames: usize,
         sample_format: PhantomData<S>,
@@ -145,6 +330,23 @@ pub mod data {
         sample_format: PhantomData<S>,
     }
 
+    /// An iterator produced by a `NonInterleaved`, yielding a mutable view of each frame across
+    /// all channels.
+    pub struct PlanarFramesMut<'a, S: 'a> {
+        buffers: &'a [sys::AudioBuffer],
+        frame: usize,
+        frames: usize,
+        sample_format: PhantomData<&'a mut S>,
+    }
+
+    /// A mutable view of a single frame of a `NonInterleaved` buffer, with a sample for each
+    /// channel.
+    pub struct PlanarFrameMut<'a, S: 'a> {
+        buffers: &'a [sys::AudioBuffer],
+        frame: usize,
+        sample_format: PhantomData<&'a mut S>,
+    }
+
     unsafe impl<S> Send for NonInterleaved<S> where S: Send {}
 
     impl<'a, S> Iterator for Channels<'a, S> {
@@ -171,12 +373,92 @@ pub mod data {
         }
     }
 
+    impl<'a, S> Iterator for PlanarFramesMut<'a, S> {
+        type Item = PlanarFrameMut<'a, S>;
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.frame >= self.frames {
+                return None;
+            }
+            // Each frame refers to a distinct sample within each buffer, so the views never alias.
+            let frame = PlanarFrameMut {
+                buffers: self.buffers,
+                frame: self.frame,
+                sample_format: PhantomData,
+            };
+            self.frame += 1;
+            Some(frame)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = self.frames - self.frame;
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl<'a, S> ExactSizeIterator for PlanarFramesMut<'a, S> {}
+
+    impl<'a, S> PlanarFrameMut<'a, S> {
+
+        /// The number of channels (samples) in the frame.
+        pub fn len(&self) -> usize {
+            self.buffers.len()
+        }
+
+        /// Whether or not the frame has no channels.
+        pub fn is_empty(&self) -> bool {
+            self.buffers.is_empty()
+        }
+
+        /// A reference to the sample of the given channel.
+        pub fn get(&self, channel: usize) -> Option<&S> {
+            self.buffers.get(channel).map(|buffer| {
+                unsafe { &*(buffer.mData as *const S).add(self.frame) }
+            })
+        }
+
+        /// A mutable reference to the sample of the given channel.
+        pub fn get_mut(&mut self, channel: usize) -> Option<&mut S> {
+            let frame = self.frame;
+            self.buffers.get(channel).map(|buffer| {
+                unsafe { &mut *(buffer.mData as *mut S).add(frame) }
+            })
+        }
+
+        /// Set the sample of every channel to the given sample.
+        pub fn fill(&mut self, sample: S)
+            where S: Copy,
+        {
+            for channel in 0..self.len() {
+                *self.get_mut(channel).unwrap() = sample;
+            }
+        }
+
+    }
+
     impl<S> NonInterleaved<S> {
 
+        /// The number of channels in the buffer.
+        pub fn num_channels(&self) -> usize {
+            self.num_buffers
+        }
+
+        /// The number of frames in each channel.
+        pub fn num_frames(&self) -> usize {
+            self.frames
+        }
+
+        fn buffers(&self) -> &[sys::AudioBuffer] {
+            unsafe { slice::from_raw_parts(self.buffers, self.num_buffers) }
+        }
+
+        fn buffers_mut(&mut self) -> &mut [sys::AudioBuffer] {
+            unsafe { slice::from_raw_parts_mut(self.buffers, self.num_buffers) }
+        }
+
         /// An iterator yielding a reference to each channel in the array.
         pub fn channels(&self) -> Channels<S> {
             Channels {
-                buffers: self.buffers.iter(),
+                buffers: self.buffers().iter(),
                 frames: self.frames,
                 sample_format: PhantomData,
             }
@@ -184,13 +466,112 @@ pub mod data {
 
         /// An iterator yielding a mutable reference to each channel in the array.
         pub fn channels_mut(&mut self) -> ChannelsMut<S> {
+            let frames = self.frames;
             ChannelsMut {
-                buffers: self.buffers.iter_mut(),
-                frames: self.frames,
+                buffers: self.buffers_mut().iter_mut(),
+                frames: frames,
+                sample_format: PhantomData,
+            }
+        }
+
+        /// A reference to the channel at the given index.
+        pub fn channel(&self, index: usize) -> Option<&[S]> {
+            self.channels().nth(index)
+        }
+
+        /// A mutable reference to the channel at the given index.
+        pub fn channel_mut(&mut self, index: usize) -> Option<&mut [S]> {
+            self.channels_mut().nth(index)
+        }
+
+        /// An iterator yielding a mutable view of each frame in the buffer.
+        ///
+        /// Each frame has a sample for each channel, allowing the same frame to be written to
+        /// every channel without indexing each channel separately.
+        pub fn frames_mut(&mut self) -> PlanarFramesMut<S> {
+            let frames = self.frames;
+            PlanarFramesMut {
+                buffers: self.buffers(),
+                frame: 0,
+                frames: frames,
                 sample_format: PhantomData,
             }
         }
 
+        /// An iterator yielding each channel of `self` zipped with the corresponding channel of
+        /// `other`.
+        ///
+        /// Useful for processing one buffer into another channel by channel.
+        pub fn zip_channels_mut<'a, T>(&'a mut self, other: &'a NonInterleaved<T>)
+            -> iter::Zip<ChannelsMut<'a, S>, Channels<'a, T>>
+        {
+            self.channels_mut().zip(other.channels())
+        }
+
+        /// Copy the samples of each channel of `other` into the corresponding channel of `self`.
+        ///
+        /// Only the channels and frames common to both buffers are copied.
+        pub fn copy_from(&mut self, other: &NonInterleaved<S>)
+            where S: Copy,
+        {
+            for (dst, src) in self.zip_channels_mut(other) {
+                let len = cmp::min(dst.len(), src.len());
+                dst[..len].copy_from_slice(&src[..len]);
+            }
+        }
+
+        /// Set every sample of every channel to the given sample.
+        pub fn fill(&mut self, sample: S)
+            where S: Copy,
+        {
+            for channel in self.channels_mut() {
+                for s in channel {
+                    *s = sample;
+                }
+            }
+        }
+
+        /// Fill every channel with silence and insert the `OUTPUT_IS_SILENCE` flag, hinting to
+        /// the audio unit that the buffer does not need to be processed.
+        pub fn silence(&mut self, flags: &mut Handle)
+            where S: Sample,
+        {
+            self.fill(S::equilibrium());
+            flags.insert(ActionFlags::OUTPUT_IS_SILENCE);
+        }
+
+        /// Convert each channel to the given `SampleFormat`, writing the samples to the
+        /// corresponding buffer in `dst` as native-endian bytes.
+        ///
+        /// If `dither` is `Some` and the conversion reduces the bit depth, TPDF dither is applied.
+        pub fn convert_into<B>(&self,
+                               dst_format: SampleFormat,
+                               dst: &mut [B],
+                               mut dither: Option<&mut Tpdf>)
+            where S: Sample,
+                  B: AsMut<[u8]>,
+        {
+            for (channel, dst) in self.channels().zip(dst.iter_mut()) {
+                let dither = dither.as_mut().map(|d| &mut **d);
+                sample_format::convert_slice_to_bytes(channel, dst_format, dst.as_mut(), dither);
+            }
+        }
+
+        /// Fill each channel with the native-endian samples of the given `SampleFormat` read from
+        /// the corresponding buffer in `src`, converting each to `S`.
+        pub fn convert_from<B>(&mut self,
+                               src_format: SampleFormat,
+                               src: &[B],
+                               mut dither: Option<&mut Tpdf>)
+            where S: Sample,
+                  B: AsRef<[u8]>,
+        {
+            for (channel, src) in self.channels_mut().zip(src.iter()) {
+                let dither = dither.as_mut().map(|d| &mut **d);
+                sample_format::convert_bytes_to_slice(src_format, src.as_ref(), channel, dither);
+            }
+        }
+
     }
 
     // Implementation for a non-interleaved linear PCM audio format.
@@ -198,22 +579,34 @@ pub mod data {
         where S: Sample,
     {
         fn does_stream_format_match(format: &StreamFormat) -> bool {
-            // TODO: This is never set, even though the default ABSD on OS X is non-interleaved!
-            // Should really investigate why this is.
-            // format.flags.contains(linear_pcm_flags::IS_NON_INTERLEAVED) &&
+            format.layout == Layout::NonInterleaved &&
+                format.unpacked_bytes_per_sample.is_none() &&
                 S::sample_format().does_match_flags(format.flags)
         }
 
         #[allow(non_snake_case)]
-        unsafe fn from_input_proc_args(frames: u32, io_data: *mut sys::AudioBufferList) -> Self {
-            let ptr = (*io_data).mBuffers.as_ptr() as *mut sys::AudioBuffer;
-            let len = (*io_data).mNumberBuffers as usize;
-            let buffers = slice::from_raw_parts_mut(ptr, len);
-            NonInterleaved {
+        unsafe fn from_input_proc_args(frames: u32, io_data: *mut sys::AudioBufferList)
+            -> Result<Self, Error>
+        {
+            let buffers = (*io_data).mBuffers.as_mut_ptr();
+            let num_buffers = (*io_data).mNumberBuffers as usize;
+
+            // Ensure that each buffer holds a single channel, as the channels are indexed by
+            // buffer, and that it is large enough to hold the given number of frames.
+            for i in 0..num_buffers {
+                let sys::AudioBuffer { mNumberChannels, mDataByteSize, .. } = *buffers.add(i);
+                let expected_size = ::std::mem::size_of::<S>() * frames as usize;
+                if mNumberChannels != 1 || (mDataByteSize as usize) < expected_size {
+                    return Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount);
+                }
+            }
+
+            Ok(NonInterleaved {
                 buffers: buffers,
+                num_buffers: num_buffers,
                 frames: frames as usize,
                 sample_format: PhantomData,
-            }
+            })
         }
     }
 
@@ -388,17 +781,29 @@ pub mod action_flags {
 }
 
 
-impl AudioUnit {
+impl<S> AudioUnit<S> {
     /// Pass a render callback (aka "Input Procedure") to the **AudioUnit**.
-    pub fn set_render_callback<F, D>(&mut self, mut f: F) -> Result<(), Error>
+    ///
+    /// The callback provides the input of the given element, e.g. `Element::Output` for I/O units
+    /// or the index of an input bus for mixers. The `Data` type must match the stream format of
+    /// the `Input` scope of that element.
+    ///
+    /// Each element may have its own callback, allowing every input bus of a mixer to be fed
+    /// independently. Setting a callback replaces any callback previously set on the same
+    /// element. As Core Audio may still be calling the replaced callback on the render thread, it
+    /// is only freed once the **AudioUnit** is disposed of.
+    ///
+    /// If the callback returns an `Err`, its `OSStatus` is returned to Core Audio and recorded as
+    /// the [**last_callback_error**](./struct.AudioUnit#method.last_callback_error). Custom status
+    /// codes may be returned via `Error::Unknown`.
+    pub fn set_render_callback<F, D>(&mut self, elem: Element, mut f: F) -> Result<(), Error>
     where
-        F: FnMut(Args<D>) -> Result<(), ()> + 'static,
+        F: for<'a> FnMut(Args<'a, D>) -> Result<(), Error> + 'static,
         D: Data,
     {
         // First, we'll retrieve the stream format so that we can ensure that the given callback
         // format matches the audio unit's format.
-        let id = sys::kAudioUnitProperty_StreamFormat;
-        let asbd = try!(self.get_property(id, Scope::Output, Element::Output));
+        let asbd = try!(self.get_scoped(property::StreamFormat, Scope::Input, elem));
         let stream_format = super::StreamFormat::from_asbd(asbd)?;
 
         // If the stream format does not match, return an error indicating this.
@@ -411,38 +816,45 @@ impl AudioUnit {
         //
         // This allows us to take advantage of rust's type system and provide format-specific
         // `Args` types which can be checked at compile time.
+        let last_error = self.last_callback_error.clone();
         let input_proc_fn = move |io_action_flags: *mut sys::AudioUnitRenderActionFlags,
                                   in_time_stamp: *const sys::AudioTimeStamp,
                                   in_bus_number: sys::UInt32,
                                   in_number_frames: sys::UInt32,
                                   io_data: *mut sys::AudioBufferList| -> sys::OSStatus
         {
-            let args = unsafe {
-                let data = D::from_input_proc_args(in_number_frames, io_data);
-                let flags = action_flags::Handle::from_ptr(io_action_flags);
-                Args {
-                    data: data,
-                    time_stamp: *in_time_stamp,
-                    flags: flags,
-                    bus_number: in_bus_number as u32,
-                    num_frames: in_number_frames as usize,
-                }
+            // The data and flags live on the stack for the duration of this call only, and the
+            // callback merely borrows them.
+            let mut data = match unsafe { D::from_input_proc_args(in_number_frames, io_data) } {
+                Err(err) => return report_error(&last_error, err),
+                Ok(data) => data,
+            };
+            let mut flags = action_flags::Handle::from_ptr(io_action_flags);
+            let args = Args {
+                data: &mut data,
+                time_stamp: unsafe { *in_time_stamp },
+                flags: &mut flags,
+                bus_number: in_bus_number as u32,
+                num_frames: in_number_frames as usize,
             };
 
             match f(args) {
                 Ok(()) => 0 as sys::OSStatus,
-                Err(()) => error::Error::Unspecified.to_os_status(),
+                Err(err) => report_error(&last_error, err),
             }
         };
 
         let input_proc_fn_wrapper = Box::new(InputProcFnWrapper {
             callback: Box::new(input_proc_fn),
+            poisoned: false,
+            panic_payload: self.callback_panic.clone(),
+            silence_byte: silence_byte(&stream_format),
         });
 
         // Setup render callback. Notice that we relinquish ownership of the Callback
         // here so that it can be used as the C render callback via a void pointer.
-        // We do however store the *mut so that we can convert back to a Box<InputProcFnWrapper>
-        // within our AudioUnit's Drop implementation (otherwise it would leak).
+        // We do however store the *mut in the element's entry of `render_callbacks` so that we can
+        // convert back to a Box<InputProcFnWrapper> when it is freed (otherwise it would leak).
         let input_proc_fn_wrapper_ptr = Box::into_raw(input_proc_fn_wrapper) as *mut c_void;
 
         let render_callback = sys::AURenderCallbackStruct {
@@ -450,28 +862,33 @@ impl AudioUnit {
             inputProcRefCon: input_proc_fn_wrapper_ptr,
         };
 
-        self.set_property(
-            sys::kAudioUnitProperty_SetRenderCallback,
-            Scope::Input,
-            Element::Output,
-            Some(&render_callback),
-        )?;
+        let id = property::SetRenderCallback;
+        let callback = input_proc_fn_wrapper_ptr as *mut InputProcFnWrapper;
+        if let Err(err) = self.set_scoped(id, Scope::Input, elem, &render_callback) {
+            unsafe { drop(Box::from_raw(callback)); }
+            return Err(err);
+        }
 
-        self.free_render_callback();
-        self.maybe_render_callback = Some(input_proc_fn_wrapper_ptr as *mut InputProcFnWrapper);
+        // Retire the callback previously installed on this element, if any.
+        if let Some(callback) = self.render_callbacks.insert(elem, callback) {
+            self.retired_callbacks.push(callback);
+        }
         Ok(())
     }
 
     /// Pass an input callback (aka "Input Procedure") to the **AudioUnit**.
-    pub fn set_input_callback<F, D>(&mut self, mut f: F) -> Result<(), Error>
+    ///
+    /// The callback is called with the input rendered from the given element of an I/O unit,
+    /// usually `Element::Input`. Setting a callback replaces any previously set input callback,
+    /// which is freed once the **AudioUnit** is disposed of.
+    pub fn set_input_callback<F, D>(&mut self, elem: Element, mut f: F) -> Result<(), Error>
     where
-        F: FnMut(Args<D>) -> Result<(), ()> + 'static,
+        F: for<'a> FnMut(Args<'a, D>) -> Result<(), Error> + 'static,
         D: Data,
     {
         // First, we'll retrieve the stream format so that we can ensure that the given callback
         // format matches the audio unit's format.
-        let id = sys::kAudioUnitProperty_StreamFormat;
-        let asbd = self.get_property(id, Scope::Input, Element::Input)?;
+        let asbd = self.get_scoped(property::StreamFormat, Scope::Input, elem)?;
         let stream_format = super::StreamFormat::from_asbd(asbd)?;
 
         // If the stream format does not match, return an error indicating this.
@@ -481,32 +898,35 @@ impl AudioUnit {
 
         // Pre-allocate a buffer list for input stream.
         //
-        // First, get the current buffer size for pre-allocating the `AudioBuffer`s.
-        let id = sys::kAudioDevicePropertyBufferFrameSize;
-        let mut buffer_frame_size: u32 = self.get_property(id, Scope::Global, Element::Output)?;
-        let mut data: Vec<u8> = vec![];
-        let sample_bytes = stream_format.sample_format.size_in_bytes();
-        let n_channels = stream_format.channels_per_frame;
-        let data_byte_size = buffer_frame_size * sample_bytes as u32 * n_channels;
-        data.reserve_exact(data_byte_size as usize);
-        let audio_buffer = sys::AudioBuffer {
-            mDataByteSize: data_byte_size,
-            mNumberChannels: n_channels,
-            mData: data.as_mut_ptr() as *mut _,
-        };
-        unsafe {
-            data.set_len(data_byte_size as usize);
-        }
-        // Relieve ownership of the `Vec` until we're ready to drop the `AudioBufferList`.
-        mem::forget(data);
-        let audio_buffer_list = Box::new(sys::AudioBufferList {
-            mNumberBuffers: 1,
-            mBuffers: [audio_buffer],
-        });
-
-        // Relinquish ownership of the audio buffer list. Instead, we'll store a raw pointer and
-        // convert it back into a `Box` when `free_input_callback` is next called.
-        let audio_buffer_list_ptr = Box::into_raw(audio_buffer_list);
+        // Allocate for the largest number of frames that the audio unit may render at once, so
+        // that the buffers never need to be reallocated on the render thread.
+        let max_frames = try!(max_frames_per_render(self.instance));
+        let buffer_list = AudioBufferListBuf::from_stream_format(&stream_format, max_frames);
+        let buffer_list = Arc::new(Mutex::new(buffer_list));
+        // The number of bytes of each frame within each of the buffers.
+        let bytes_per_frame = stream_format.bytes_per_frame() as usize;
+
+        // Either number of frames may be changed later on, e.g. by the system when the device's
+        // buffer size changes, so grow the buffers off the render thread whenever they are.
+        let ids = [sys::kAudioDevicePropertyBufferFrameSize,
+                   sys::kAudioUnitProperty_MaximumFramesPerSlice];
+        let mut listeners = Vec::with_capacity(ids.len());
+        for &id in ids.iter() {
+            // The listener is only called while the instance is alive.
+            let instance = self.instance as usize;
+            let buffer_list = buffer_list.clone();
+            let grow_buffers = move || {
+                if let Ok(max_frames) = max_frames_per_render(instance as sys::AudioUnit) {
+                    let mut buffer_list = buffer_list.lock().unwrap_or_else(|err| err.into_inner());
+                    let bytes = max_frames * bytes_per_frame;
+                    if bytes > buffer_list.capacity() {
+                        buffer_list.reserve(bytes);
+                    }
+                }
+            };
+            let elem = Element::Output;
+            listeners.push(try!(self.add_property_listener(id, Scope::Global, elem, grow_buffers)));
+        }
 
         // Here, we call the given input callback function within a closure that matches the
         // arguments of the required coreaudio "input_proc".
@@ -514,48 +934,33 @@ impl AudioUnit {
         // This allows us to take advantage of rust's type system and provide format-specific
         // `Args` types which can be checked at compile time.
         let audio_unit = self.instance;
+        let last_error = self.last_callback_error.clone();
         let input_proc_fn = move |io_action_flags: *mut sys::AudioUnitRenderActionFlags,
                                   in_time_stamp: *const sys::AudioTimeStamp,
                                   in_bus_number: sys::UInt32,
                                   in_number_frames: sys::UInt32,
                                   _io_data: *mut sys::AudioBufferList| -> sys::OSStatus
         {
-            // If the buffer size has changed, ensure the AudioBuffer is the correct size.
-            if buffer_frame_size != in_number_frames {
-                unsafe {
-                    // Retrieve the up-to-date stream format.
-                    let id = sys::kAudioUnitProperty_StreamFormat;
-                    let asbd = match super::get_property(audio_unit, id, Scope::Input, Element::Output) {
-                        Err(err) => return err.to_os_status(),
-                        Ok(asbd) => asbd,
-                    };
-                    let stream_format = match super::StreamFormat::from_asbd(asbd) {
-                        Err(err) => return err.to_os_status(),
-                        Ok(fmt) => fmt,
-                    };
-                    let sample_bytes = stream_format.sample_format.size_in_bytes();
-                    let n_channels = stream_format.channels_per_frame;
-                    let data_byte_size = in_number_frames as usize
-                        * sample_bytes
-                        * n_channels as usize;
-                    let ptr = (*audio_buffer_list_ptr).mBuffers.as_ptr() as *const sys::AudioBuffer;
-                    let len = (*audio_buffer_list_ptr).mNumberBuffers as usize;
-                    let buffers: &[sys::AudioBuffer] = slice::from_raw_parts(ptr, len);
-                    for &buffer in buffers {
-                        let ptr = buffer.mData as *mut u8;
-                        let len = buffer.mDataByteSize as usize;
-                        let cap = len;
-                        let mut vec = Vec::from_raw_parts(ptr, len, cap);
-                        if len < data_byte_size {
-                            vec.reserve_exact(data_byte_size - len);
-                        } else if len > data_byte_size {
-                            vec.truncate(data_byte_size);
-                        }
-                        mem::forget(vec);
-                    }
-                }
-                buffer_frame_size = in_number_frames;
+            // The buffers are only locked elsewhere while they are being grown, in which case
+            // there is nothing to render into.
+            let mut buffer_list = match buffer_list.try_lock() {
+                Ok(buffer_list) => buffer_list,
+                Err(TryLockError::Poisoned(err)) => err.into_inner(),
+                Err(TryLockError::WouldBlock) => {
+                    let err = Error::AudioUnit(AudioUnitError::TooManyFramesToProcess);
+                    return report_error(&last_error, err);
+                },
+            };
+
+            // Size the buffers for the number of frames requested. `AudioUnitRender` may also
+            // have changed their sizes during the previous call.
+            let bytes = in_number_frames as usize * bytes_per_frame;
+            if bytes > buffer_list.capacity() {
+                let err = Error::AudioUnit(AudioUnitError::TooManyFramesToProcess);
+                return report_error(&last_error, err);
             }
+            buffer_list.set_data_byte_size(bytes);
+            let audio_buffer_list_ptr = buffer_list.as_mut_ptr();
 
             unsafe {
                 let status = sys::AudioUnitRender(
@@ -566,37 +971,44 @@ impl AudioUnit {
                     in_number_frames,
                     audio_buffer_list_ptr,
                 );
-                if status != 0 {
-                    return status;
+                if let Err(err) = Error::from_os_status(status) {
+                    return report_error(&last_error, err);
                 }
             }
 
-            let args = unsafe {
-                let data = D::from_input_proc_args(in_number_frames, audio_buffer_list_ptr);
-                let flags = action_flags::Handle::from_ptr(io_action_flags);
-                Args {
-                    data: data,
-                    time_stamp: *in_time_stamp,
-                    flags: flags,
-                    bus_number: in_bus_number as u32,
-                    num_frames: in_number_frames as usize,
-                }
+            // The data and flags live on the stack for the duration of this call only, and the
+            // callback merely borrows them.
+            let data = unsafe { D::from_input_proc_args(in_number_frames, audio_buffer_list_ptr) };
+            let mut data = match data {
+                Err(err) => return report_error(&last_error, err),
+                Ok(data) => data,
+            };
+            let mut flags = action_flags::Handle::from_ptr(io_action_flags);
+            let args = Args {
+                data: &mut data,
+                time_stamp: unsafe { *in_time_stamp },
+                flags: &mut flags,
+                bus_number: in_bus_number as u32,
+                num_frames: in_number_frames as usize,
             };
 
             match f(args) {
                 Ok(()) => 0 as sys::OSStatus,
-                Err(()) => error::Error::Unspecified.to_os_status(),
+                Err(err) => report_error(&last_error, err),
             }
         };
 
         let input_proc_fn_wrapper = Box::new(InputProcFnWrapper {
             callback: Box::new(input_proc_fn),
+            poisoned: false,
+            panic_payload: self.callback_panic.clone(),
+            silence_byte: silence_byte(&stream_format),
         });
 
         // Setup input callback. Notice that we relinquish ownership of the Callback
         // here so that it can be used as the C render callback via a void pointer.
         // We do however store the *mut so that we can convert back to a Box<InputProcFnWrapper>
-        // within our AudioUnit's Drop implementation (otherwise it would leak).
+        // when the **AudioUnit** is disposed of (otherwise it would leak).
         let input_proc_fn_wrapper_ptr = Box::into_raw(input_proc_fn_wrapper) as *mut c_void;
 
         let render_callback = sys::AURenderCallbackStruct {
@@ -604,63 +1016,145 @@ impl AudioUnit {
             inputProcRefCon: input_proc_fn_wrapper_ptr,
         };
 
-        self.set_property(
-            sys::kAudioOutputUnitProperty_SetInputCallback,
-            Scope::Global,
-            Element::Output,
-            Some(&render_callback),
-        )?;
+        let callback = input_proc_fn_wrapper_ptr as *mut InputProcFnWrapper;
+        if let Err(err) = self.set(property::SetInputCallback, &render_callback) {
+            unsafe { drop(Box::from_raw(callback)); }
+            return Err(err);
+        }
 
-        let input_callback = super::InputCallback {
-            buffer_list: audio_buffer_list_ptr,
-            callback: input_proc_fn_wrapper_ptr as *mut InputProcFnWrapper,
-        };
-        self.free_input_callback();
-        self.maybe_input_callback = Some(input_callback);
+        // Retire the previous input callback, if any, and replace the listeners of its buffers.
+        if let Some(callback) = mem::replace(&mut self.maybe_input_callback, Some(callback)) {
+            self.retired_callbacks.push(callback);
+        }
+        self.input_buffer_listeners = listeners;
         Ok(())
     }
 
-    /// Retrieves ownership over the render callback and returns it where it can be re-used or
-    /// safely dropped.
-    pub fn free_render_callback(&mut self) -> Option<Box<InputProcFnWrapper>> {
-        if let Some(callback) = self.maybe_render_callback.take() {
-            // Here, we transfer ownership of the callback back to the current scope so that it
-            // is dropped and cleaned up. Without this line, we would leak the Boxed callback.
-            let callback: Box<InputProcFnWrapper> = unsafe { Box::from_raw(callback) };
-            return Some(callback);
-        }
-        None
+    /// The most recent error returned by (or encountered while preparing the arguments of) a
+    /// render or input callback, if any.
+    ///
+    /// The error is recorded without blocking the render thread, so this may be polled from
+    /// another thread for diagnostics.
+    ///
+    /// Only the error's `OSStatus` is recorded. Errors with a status of their own, including
+    /// `Error::Unknown`, are returned as they were reported, while errors specific to this crate
+    /// (such as `RenderCallbackBufferSizeDoesNotMatchFrameCount`) share the unspecified status
+    /// and are returned as `Error::Unspecified`.
+    pub fn last_callback_error(&self) -> Option<Error> {
+        let status = self.last_callback_error.load(Ordering::Relaxed);
+        Error::from_os_status(status).err()
     }
 
-    /// Retrieves ownership over the input callback and returns it where it can be re-used or
-    /// safely dropped.
-    pub fn free_input_callback(&mut self) -> Option<Box<InputProcFnWrapper>> {
-        if let Some(input_callback) = self.maybe_input_callback.take() {
-            let super::InputCallback { buffer_list, callback } = input_callback;
-            unsafe {
-                // Take ownership over the AudioBufferList in order to safely free it.
-                let buffer_list: Box<sys::AudioBufferList> = Box::from_raw(buffer_list);
-                // Free the allocated data from the individual audio buffers.
-                let ptr = buffer_list.mBuffers.as_ptr() as *const sys::AudioBuffer;
-                let len = buffer_list.mNumberBuffers as usize;
-                let buffers: &[sys::AudioBuffer] = slice::from_raw_parts(ptr, len);
-                for &buffer in buffers {
-                    let ptr = buffer.mData as *mut u8;
-                    let len = buffer.mDataByteSize as usize;
-                    let cap = len;
-                    let _ = Vec::from_raw_parts(ptr, len, cap);
-                }
-                // Take ownership over the callback so that it can be freed.
-                let callback: Box<InputProcFnWrapper> = Box::from_raw(callback);
-                return Some(callback);
-            }
+    /// Returns the most recent callback error, if any, and clears it.
+    pub fn take_last_callback_error(&self) -> Option<Error> {
+        let status = self.last_callback_error.swap(0, Ordering::Relaxed);
+        Error::from_os_status(status).err()
+    }
+
+    /// Whether or not one of the **AudioUnit**'s render or input callbacks has panicked and the
+    /// panic has not yet been taken via `take_callback_panic`.
+    ///
+    /// A callback that panics is poisoned: it is never called again and its output is silenced.
+    /// Setting a new callback replaces the poisoned one.
+    ///
+    /// As the render thread must not block, a panic is not recorded if it occurs while the
+    /// payload is being accessed, e.g. via this method, on another thread.
+    pub fn has_callback_panicked(&self) -> bool {
+        let payload = self.callback_panic.lock().unwrap_or_else(|err| err.into_inner());
+        payload.is_some()
+    }
+
+    /// Takes the payload of the first panic that occurred within one of the **AudioUnit**'s
+    /// render or input callbacks, if any.
+    ///
+    /// The payload may be passed to `std::panic::resume_unwind` in order to propagate the panic
+    /// on the owning thread.
+    pub fn take_callback_panic(&self) -> Option<Box<dyn Any + Send>> {
+        let mut payload = self.callback_panic.lock().unwrap_or_else(|err| err.into_inner());
+        payload.take()
+    }
+
+    /// Removes the render callback of the given element, if any, returning whether or not there
+    /// was one.
+    ///
+    /// The callback is uninstalled immediately. As Core Audio may still be calling it on the
+    /// render thread, it is only freed once the **AudioUnit** is disposed of.
+    pub fn free_render_callback(&mut self, elem: Element) -> Result<bool, Error> {
+        let callback = match self.render_callbacks.remove(&elem) {
+            Some(callback) => callback,
+            None => return Ok(false),
+        };
+        // The callback is retired even if uninstalling it fails, as it may still be installed.
+        self.retired_callbacks.push(callback);
+        try!(self.set_scoped(property::SetRenderCallback, Scope::Input, elem, &no_callback()));
+        Ok(true)
+    }
+
+    /// Removes the render callbacks of every element.
+    ///
+    /// Every callback is removed even if uninstalling one of them fails, in which case the first
+    /// error is returned.
+    pub fn free_render_callbacks(&mut self) -> Result<(), Error> {
+        let elems: Vec<Element> = self.render_callbacks.keys().cloned().collect();
+        let mut result = Ok(());
+        for elem in elems {
+            result = result.and(self.free_render_callback(elem).map(|_| ()));
         }
-        None
+        result
+    }
+
+    /// Removes the input callback, if any, returning whether or not there was one.
+    ///
+    /// Like [**free_render_callback**](./struct.AudioUnit#method.free_render_callback), the
+    /// callback is only freed once the **AudioUnit** is disposed of.
+    pub fn free_input_callback(&mut self) -> Result<bool, Error> {
+        let callback = match self.maybe_input_callback.take() {
+            Some(callback) => callback,
+            None => return Ok(false),
+        };
+        self.retired_callbacks.push(callback);
+        try!(self.set(property::SetInputCallback, &no_callback()));
+        Ok(true)
     }
 }
 
 
+/// The value of the `SetRenderCallback` and `SetInputCallback` properties that uninstalls the
+/// callback.
+fn no_callback() -> sys::AURenderCallbackStruct {
+    sys::AURenderCallbackStruct {
+        inputProc: None,
+        inputProcRefCon: ptr::null_mut(),
+    }
+}
+
+
+/// The largest number of frames that the audio unit may render at once, i.e. the larger of its
+/// buffer frame size and its maximum frames per slice.
+fn max_frames_per_render(instance: sys::AudioUnit) -> Result<usize, Error> {
+    let (scope, elem) = (Scope::Global, Element::Output);
+    let id = sys::kAudioDevicePropertyBufferFrameSize;
+    let buffer_frame_size: u32 = try!(super::get_property(instance, id, scope, elem));
+    let id = sys::kAudioUnitProperty_MaximumFramesPerSlice;
+    let max_frames: u32 = super::get_property(instance, id, scope, elem).unwrap_or(0);
+    Ok(cmp::max(buffer_frame_size, max_frames) as usize)
+}
+
+
+/// Record the error in the **AudioUnit**'s last callback error slot and return its `OSStatus`.
+///
+/// Called from the render thread, so this must never block.
+fn report_error(last_error: &AtomicI32, err: Error) -> sys::OSStatus {
+    let status = err.to_os_status();
+    last_error.store(status, Ordering::Relaxed);
+    status
+}
+
+
 /// Callback procedure that will be called each time our audio_unit requests audio.
+///
+/// Panics must not unwind across the FFI boundary, so the callback is called within
+/// `catch_unwind`. If it panics, the callback is poisoned and the output is silenced from then on.
 extern "C" fn input_proc(in_ref_con: *mut c_void,
                          io_action_flags: *mut sys::AudioUnitRenderActionFlags,
                          in_time_stamp: *const sys::AudioTimeStamp,
@@ -668,12 +1162,67 @@ extern "C" fn input_proc(in_ref_con: *mut c_void,
                          in_number_frames: sys::UInt32,
                          io_data: *mut sys::AudioBufferList) -> sys::OSStatus
 {
-    let wrapper = in_ref_con as *mut InputProcFnWrapper;
-    unsafe {
-        (*(*wrapper).callback)(io_action_flags,
-                               in_time_stamp,
-                               in_bus_number,
-                               in_number_frames,
-                               io_data)
+    let wrapper = unsafe { &mut *(in_ref_con as *mut InputProcFnWrapper) };
+    if !wrapper.poisoned {
+        let result = {
+            let callback = &mut wrapper.callback;
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                callback(io_action_flags, in_time_stamp, in_bus_number, in_number_frames, io_data)
+            }))
+        };
+        match result {
+            Ok(status) => return status,
+            Err(payload) => {
+                wrapper.poisoned = true;
+                // Blocking on the render thread could cause a glitch, so the payload is dropped
+                // if the lock is contended.
+                let panic_payload = match wrapper.panic_payload.try_lock() {
+                    Ok(panic_payload) => Some(panic_payload),
+                    Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+                    Err(TryLockError::WouldBlock) => None,
+                };
+                // Keep the first panic, as any later panics are likely a consequence of it.
+                if let Some(mut panic_payload) = panic_payload {
+                    if panic_payload.is_none() {
+                        *panic_payload = Some(payload);
+                    }
+                }
+            },
+        }
+    }
+    unsafe { silence(io_action_flags, io_data, wrapper.silence_byte); }
+    0
+}
+
+/// The byte that represents silence in every sample of the given format.
+///
+/// Unsigned samples are centred around half of their range, while zero is silence for all of the
+/// other formats.
+fn silence_byte(format: &StreamFormat) -> u8 {
+    match format.sample_format {
+        SampleFormat::U8 => 0x80,
+        _ => 0,
+    }
+}
+
+/// Fill the given buffers with the given silence byte and flag them as silent.
+unsafe fn silence(io_action_flags: *mut sys::AudioUnitRenderActionFlags,
+                  io_data: *mut sys::AudioBufferList,
+                  silence_byte: u8)
+{
+    if !io_data.is_null() {
+        let ptr = (*io_data).mBuffers.as_ptr() as *const sys::AudioBuffer;
+        let len = (*io_data).mNumberBuffers as usize;
+        let buffers: &[sys::AudioBuffer] = slice::from_raw_parts(ptr, len);
+        for buffer in buffers {
+            if !buffer.mData.is_null() {
+                let len = buffer.mDataByteSize as usize;
+                ptr::write_bytes(buffer.mData as *mut u8, silence_byte, len);
+            }
+        }
+    }
+    if !io_action_flags.is_null() {
+        let mut flags = action_flags::Handle::from_ptr(io_action_flags);
+        flags.insert(ActionFlags::OUTPUT_IS_SILENCE);
     }
 }
@@ -1,73 +1,457 @@
+use std::mem;
+use std::ptr;
 use super::audio_format::{self, LinearPcmFlags};
 
 
 /// Dynamic representation of audio data sample format.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SampleFormat {
+    /// 32-bit floating point.
     F32,
+    /// 64-bit floating point.
+    F64,
+    /// 32-bit signed integer.
     I32,
+    /// Packed 24-bit signed integer.
+    I24,
+    /// 16-bit signed integer.
     I16,
+    /// 8-bit signed integer.
     I8,
+    /// 8-bit unsigned integer.
+    U8,
+    /// 8.24 fixed point, stored as a 32-bit signed integer with 24 fractional bits.
+    ///
+    /// This is the canonical audio unit sample format on iOS.
+    Fixed8_24,
 }
 
 impl SampleFormat {
 
+    /// Whether or not the given `LinearPCM` flags describe samples of this format.
     pub fn does_match_flags(&self, flags: audio_format::LinearPcmFlags) -> bool {
         let is_float = flags.contains(LinearPcmFlags::IS_FLOAT);
         let is_signed_integer = flags.contains(LinearPcmFlags::IS_SIGNED_INTEGER);
+        let fraction_bits = flags.sample_fraction_bits();
         match *self {
-            SampleFormat::F32 => is_float && !is_signed_integer,
+            SampleFormat::F32 |
+            SampleFormat::F64 => is_float && !is_signed_integer,
             SampleFormat::I32 |
+            SampleFormat::I24 |
             SampleFormat::I16 |
-            SampleFormat::I8 => is_signed_integer && !is_float,
+            SampleFormat::I8 => is_signed_integer && !is_float && fraction_bits == 0,
+            SampleFormat::U8 => !is_signed_integer && !is_float,
+            SampleFormat::Fixed8_24 => is_signed_integer && !is_float && fraction_bits == 24,
         }
     }
 
-    pub fn from_flags_and_bytes_per_frame(flags: audio_format::LinearPcmFlags,
-                                          bytes_per_frame: u32) -> Option<Self>
+    /// Determine the sample format from the `LinearPCM` flags and the number of bits used to
+    /// represent each sample (the `mBitsPerChannel` field of an ASBD).
+    ///
+    /// Fixed point formats are recognised by the number of fractional bits stored within the
+    /// `FLAGS_SAMPLE_FRACTION_MASK` bits of the flags.
+    pub fn from_flags_and_bits_per_channel(flags: audio_format::LinearPcmFlags,
+                                           bits_per_channel: u32) -> Option<Self>
     {
         Some(if flags.contains(LinearPcmFlags::IS_FLOAT) {
-            SampleFormat::F32
+            match bits_per_channel {
+                32 => SampleFormat::F32,
+                64 => SampleFormat::F64,
+                _ => return None,
+            }
+        } else if flags.contains(LinearPcmFlags::IS_SIGNED_INTEGER) {
+            match (bits_per_channel, flags.sample_fraction_bits()) {
+                (8, 0) => SampleFormat::I8,
+                (16, 0) => SampleFormat::I16,
+                (24, 0) => SampleFormat::I24,
+                (32, 0) => SampleFormat::I32,
+                (32, 24) => SampleFormat::Fixed8_24,
+                _ => return None,
+            }
         } else {
-            // TODO: Check whether or not we need to consider unsigned ints and `IS_PACKED`.
-            match bytes_per_frame {
-                1 => SampleFormat::I8,
-                2 => SampleFormat::I16,
-                4 => SampleFormat::I32,
+            match bits_per_channel {
+                8 => SampleFormat::U8,
                 _ => return None,
             }
         })
     }
 
+    /// Determine the sample format from the `LinearPCM` flags and the number of bytes used to
+    /// represent each sample.
+    #[deprecated(note = "use `from_flags_and_bits_per_channel` instead")]
+    pub fn from_flags_and_bytes_per_frame(flags: audio_format::LinearPcmFlags,
+                                          bytes_per_frame: u32) -> Option<Self>
+    {
+        SampleFormat::from_flags_and_bits_per_channel(flags, bytes_per_frame.saturating_mul(8))
+    }
+
+    /// The number of bytes used to represent a single sample of this format.
     pub fn size_in_bytes(&self) -> usize {
         use std::mem::size_of;
         match *self {
             SampleFormat::F32 => size_of::<f32>(),
+            SampleFormat::F64 => size_of::<f64>(),
             SampleFormat::I32 => size_of::<i32>(),
+            SampleFormat::I24 => size_of::<I24>(),
             SampleFormat::I16 => size_of::<i16>(),
             SampleFormat::I8 => size_of::<i8>(),
+            SampleFormat::U8 => size_of::<u8>(),
+            SampleFormat::Fixed8_24 => size_of::<Fixed8_24>(),
+        }
+    }
+
+    /// Whether or not the format is a floating point format.
+    pub fn is_float(&self) -> bool {
+        match *self {
+            SampleFormat::F32 | SampleFormat::F64 => true,
+            _ => false,
+        }
+    }
+
+    /// The number of bits of precision offered by the format.
+    ///
+    /// For floating point formats this is the number of bits in the significand.
+    pub fn precision_bits(&self) -> u32 {
+        match *self {
+            SampleFormat::F32 => 24,
+            SampleFormat::F64 => 53,
+            SampleFormat::I32 => 32,
+            SampleFormat::I24 => 24,
+            SampleFormat::I16 => 16,
+            SampleFormat::I8 |
+            SampleFormat::U8 => 8,
+            SampleFormat::Fixed8_24 => 32,
+        }
+    }
+
+    // The size of the least significant bit relative to a full scale of `1.0`.
+    //
+    // Returns `None` for floating point formats as they do not have a fixed quantization step.
+    fn lsb(&self) -> Option<f64> {
+        match *self {
+            SampleFormat::F32 | SampleFormat::F64 => None,
+            SampleFormat::I32 => Some(1.0 / 2147483648.0),
+            SampleFormat::I24 => Some(1.0 / 8388608.0),
+            SampleFormat::I16 => Some(1.0 / 32768.0),
+            SampleFormat::I8 |
+            SampleFormat::U8 => Some(1.0 / 128.0),
+            SampleFormat::Fixed8_24 => Some(1.0 / 16777216.0),
         }
     }
 
 }
 
+/// A packed 24-bit signed integer sample stored as three native-endian bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct I24(pub [u8; 3]);
+
+impl I24 {
+
+    /// The smallest value that can be represented by an `I24`.
+    pub const MIN: i32 = -(1 << 23);
+    /// The largest value that can be represented by an `I24`.
+    pub const MAX: i32 = (1 << 23) - 1;
+
+    /// Create an `I24` from the lowest 24 bits of the given `i32`.
+    pub fn from_i32(i: i32) -> Self {
+        let b = i.to_le_bytes();
+        if cfg!(target_endian = "little") {
+            I24([b[0], b[1], b[2]])
+        } else {
+            I24([b[2], b[1], b[0]])
+        }
+    }
+
+    /// Sign-extend the sample to an `i32`.
+    pub fn to_i32(&self) -> i32 {
+        let b = self.0;
+        let le = if cfg!(target_endian = "little") { b } else { [b[2], b[1], b[0]] };
+        (i32::from_le_bytes([le[0], le[1], le[2], 0]) << 8) >> 8
+    }
+
+}
+
+/// An 8.24 fixed point sample.
+///
+/// The inner `i32` stores the sample value multiplied by `1 << 24`, so that the range `-1.0..1.0`
+/// maps to `-(1 << 24)..(1 << 24)` and the top 8 bits provide headroom.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct Fixed8_24(pub i32);
+
+impl Fixed8_24 {
+
+    /// The number of fractional bits.
+    pub const FRACTION_BITS: u32 = 24;
+
+}
+
 /// Audio data sample types.
-pub trait Sample {
+///
+/// All conversions between sample types pass through a canonical `f64` representation in which
+/// full scale is represented by the range `-1.0..1.0`. `f64` is wide enough to represent every
+/// other sample type without loss.
+pub trait Sample: Copy {
     /// Dynamic representation of audio data sample format.
     fn sample_format() -> SampleFormat;
+    /// The value representing silence.
+    fn equilibrium() -> Self;
+    /// Convert the sample to the canonical `f64` representation.
+    fn to_f64(self) -> f64;
+    /// Convert from the canonical `f64` representation.
+    ///
+    /// Values outside of the representable range are clamped for integer formats. The value is
+    /// rounded to the nearest representable value.
+    fn from_f64(f: f64) -> Self;
+
+    /// Convert the sample to an `f32` in the range `-1.0..1.0`.
+    fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Convert from an `f32` in the range `-1.0..1.0`.
+    fn from_f32(f: f32) -> Self {
+        Self::from_f64(f as f64)
+    }
+
+    /// Convert the sample to any other sample type.
+    fn to_sample<S: Sample>(self) -> S {
+        S::from_f64(self.to_f64())
+    }
+}
+
+// Scale, round and clamp the given canonical value to the range of an integer format.
+fn scale_round_clamp(f: f64, scale: f64, min: f64, max: f64) -> f64 {
+    (f * scale).round().max(min).min(max)
+}
+
+impl Sample for f32 {
+    fn sample_format() -> SampleFormat { SampleFormat::F32 }
+    fn equilibrium() -> Self { 0.0 }
+    fn to_f64(self) -> f64 { self as f64 }
+    fn from_f64(f: f64) -> Self { f as f32 }
+    fn to_f32(self) -> f32 { self }
+    fn from_f32(f: f32) -> Self { f }
+}
+
+impl Sample for f64 {
+    fn sample_format() -> SampleFormat { SampleFormat::F64 }
+    fn equilibrium() -> Self { 0.0 }
+    fn to_f64(self) -> f64 { self }
+    fn from_f64(f: f64) -> Self { f }
 }
 
-/// Simplified implementation of the `Sample` trait for sample types.
-macro_rules! impl_sample {
-    ($($T:ident $format:ident),* $(,)*) => {
+/// Simplified implementation of the `Sample` trait for signed integer sample types.
+macro_rules! impl_sample_int {
+    ($($T:ident $format:ident $scale:expr),* $(,)*) => {
         $(
             impl Sample for $T {
                 fn sample_format() -> SampleFormat {
                     SampleFormat::$format
                 }
+                fn equilibrium() -> Self {
+                    0
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64 / $scale
+                }
+                fn from_f64(f: f64) -> Self {
+                    scale_round_clamp(f, $scale, ::std::$T::MIN as f64, ::std::$T::MAX as f64) as $T
+                }
             }
         )*
     }
 }
 
-impl_sample!(f32 F32, i32 I32, i16 I16, i8 I8);
+impl_sample_int!(i32 I32 2147483648.0, i16 I16 32768.0, i8 I8 128.0);
+
+impl Sample for u8 {
+    fn sample_format() -> SampleFormat { SampleFormat::U8 }
+    fn equilibrium() -> Self { 128 }
+    fn to_f64(self) -> f64 { (self as f64 - 128.0) / 128.0 }
+    fn from_f64(f: f64) -> Self { (scale_round_clamp(f, 128.0, -128.0, 127.0) + 128.0) as u8 }
+}
+
+impl Sample for I24 {
+    fn sample_format() -> SampleFormat { SampleFormat::I24 }
+    fn equilibrium() -> Self { I24([0; 3]) }
+    fn to_f64(self) -> f64 { self.to_i32() as f64 / 8388608.0 }
+    fn from_f64(f: f64) -> Self {
+        I24::from_i32(scale_round_clamp(f, 8388608.0, I24::MIN as f64, I24::MAX as f64) as i32)
+    }
+}
+
+impl Sample for Fixed8_24 {
+    fn sample_format() -> SampleFormat { SampleFormat::Fixed8_24 }
+    fn equilibrium() -> Self { Fixed8_24(0) }
+    fn to_f64(self) -> f64 { self.0 as f64 / 16777216.0 }
+    fn from_f64(f: f64) -> Self {
+        let (min, max) = (::std::i32::MIN as f64, ::std::i32::MAX as f64);
+        Fixed8_24(scale_round_clamp(f, 16777216.0, min, max) as i32)
+    }
+}
+
+
+/// A triangular probability density function (TPDF) dither generator.
+///
+/// TPDF dither adds noise with an amplitude of up to one least significant bit of the target
+/// format prior to quantization. This decorrelates the quantization error from the signal, trading
+/// the harmonic distortion caused by truncation for a constant, benign noise floor.
+///
+/// The noise is produced by a small, allocation-free pseudo-random number generator, so that a
+/// `Tpdf` may be used from within a render callback.
+#[derive(Copy, Clone, Debug)]
+pub struct Tpdf {
+    state: u32,
+}
+
+impl Tpdf {
+
+    /// Construct a new dither generator with a default seed.
+    pub fn new() -> Self {
+        Tpdf::with_seed(0x9E37_79B9)
+    }
+
+    /// Construct a new dither generator with the given seed.
+    ///
+    /// A seed of `0` is replaced by the default seed as the generator would otherwise only ever
+    /// produce `0`.
+    pub fn with_seed(seed: u32) -> Self {
+        Tpdf { state: if seed == 0 { 0x9E37_79B9 } else { seed } }
+    }
+
+    // Produce the next uniformly distributed value in the range `0.0..1.0` (xorshift32).
+    fn next_uniform(&mut self) -> f64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        (x >> 8) as f64 / 16777216.0
+    }
+
+    /// Produce the next dither value in the range `-1.0..1.0`, measured in least significant bits
+    /// of the target format.
+    pub fn next(&mut self) -> f64 {
+        self.next_uniform() - self.next_uniform()
+    }
+
+}
+
+impl Default for Tpdf {
+    fn default() -> Self {
+        Tpdf::new()
+    }
+}
+
+// The size of the LSB of `B` if converting from `A` to `B` reduces the bit depth.
+fn dither_lsb<A: Sample, B: Sample>() -> Option<f64> {
+    let (a, b) = (A::sample_format(), B::sample_format());
+    if a.precision_bits() > b.precision_bits() { b.lsb() } else { None }
+}
+
+// Convert a single sample, applying dither scaled to the given LSB if there is one.
+fn convert_sample<A, B>(sample: A, lsb: Option<f64>, dither: &mut Option<&mut Tpdf>) -> B
+    where A: Sample,
+          B: Sample,
+{
+    let mut f = sample.to_f64();
+    if let (Some(lsb), &mut Some(ref mut tpdf)) = (lsb, dither) {
+        f += tpdf.next() * lsb;
+    }
+    B::from_f64(f)
+}
+
+/// Convert each sample in `src` to the sample type of `dst`.
+///
+/// Conversion stops at the end of the shorter of the two slices.
+///
+/// If `dither` is `Some` and the conversion reduces the bit depth, TPDF dither is applied prior
+/// to quantization.
+pub fn convert_slice<A, B>(src: &[A], dst: &mut [B], mut dither: Option<&mut Tpdf>)
+    where A: Sample,
+          B: Sample,
+{
+    let lsb = dither_lsb::<A, B>();
+    for (a, b) in src.iter().zip(dst.iter_mut()) {
+        *b = convert_sample(*a, lsb, &mut dither);
+    }
+}
+
+/// Convert each sample in `src` to the given `SampleFormat`, writing the native-endian bytes of
+/// each resulting sample to `dst`.
+///
+/// This allows for writing to buffers whose `SampleFormat` is only known at runtime, e.g. a
+/// buffer described by the `StreamFormat` negotiated with a device.
+///
+/// Conversion stops when either `src` is exhausted or `dst` has no room for another sample.
+pub fn convert_slice_to_bytes<A>(src: &[A],
+                                 dst_format: SampleFormat,
+                                 dst: &mut [u8],
+                                 dither: Option<&mut Tpdf>)
+    where A: Sample,
+{
+    match dst_format {
+        SampleFormat::F32 => write_bytes::<A, f32>(src, dst, dither),
+        SampleFormat::F64 => write_bytes::<A, f64>(src, dst, dither),
+        SampleFormat::I32 => write_bytes::<A, i32>(src, dst, dither),
+        SampleFormat::I24 => write_bytes::<A, I24>(src, dst, dither),
+        SampleFormat::I16 => write_bytes::<A, i16>(src, dst, dither),
+        SampleFormat::I8 => write_bytes::<A, i8>(src, dst, dither),
+        SampleFormat::U8 => write_bytes::<A, u8>(src, dst, dither),
+        SampleFormat::Fixed8_24 => write_bytes::<A, Fixed8_24>(src, dst, dither),
+    }
+}
+
+/// Read native-endian samples of the given `SampleFormat` from `src`, converting each to the
+/// sample type of `dst`.
+///
+/// Conversion stops when either `dst` is full or `src` has no more complete samples.
+pub fn convert_bytes_to_slice<B>(src_format: SampleFormat,
+                                 src: &[u8],
+                                 dst: &mut [B],
+                                 dither: Option<&mut Tpdf>)
+    where B: Sample,
+{
+    match src_format {
+        SampleFormat::F32 => read_bytes::<f32, B>(src, dst, dither),
+        SampleFormat::F64 => read_bytes::<f64, B>(src, dst, dither),
+        SampleFormat::I32 => read_bytes::<i32, B>(src, dst, dither),
+        SampleFormat::I24 => read_bytes::<I24, B>(src, dst, dither),
+        SampleFormat::I16 => read_bytes::<i16, B>(src, dst, dither),
+        SampleFormat::I8 => read_bytes::<i8, B>(src, dst, dither),
+        SampleFormat::U8 => read_bytes::<u8, B>(src, dst, dither),
+        SampleFormat::Fixed8_24 => read_bytes::<Fixed8_24, B>(src, dst, dither),
+    }
+}
+
+fn write_bytes<A, B>(src: &[A], dst: &mut [u8], mut dither: Option<&mut Tpdf>)
+    where A: Sample,
+          B: Sample,
+{
+    let lsb = dither_lsb::<A, B>();
+    let size = mem::size_of::<B>();
+    for (a, bytes) in src.iter().zip(dst.chunks_mut(size)) {
+        if bytes.len() < size {
+            break;
+        }
+        let b: B = convert_sample(*a, lsb, &mut dither);
+        unsafe { ptr::write_unaligned(bytes.as_mut_ptr() as *mut B, b) }
+    }
+}
+
+fn read_bytes<A, B>(src: &[u8], dst: &mut [B], mut dither: Option<&mut Tpdf>)
+    where A: Sample,
+          B: Sample,
+{
+    let lsb = dither_lsb::<A, B>();
+    let size = mem::size_of::<A>();
+    for (bytes, b) in src.chunks(size).zip(dst.iter_mut()) {
+        if bytes.len() < size {
+            break;
+        }
+        let a: A = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const A) };
+        *b = convert_sample(a, lsb, &mut dither);
+    }
+}
@@ -0,0 +1,177 @@
+//! A general-purpose rustification of the `AudioStreamBasicDescription` type.
+//!
+//! Unlike the **StreamFormat** type, which is specific to the `LinearPCM` data used by the audio
+//! unit API, the **AudioStreamDescription** may describe any **AudioFormat**, including compressed
+//! and packetized formats such as AAC and Apple Lossless. This makes it suitable for use with audio
+//! files and audio converters.
+//!
+//! Find the original `AudioStreamBasicDescription` reference [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Reference/CoreAudioDataTypesRef/#//apple_ref/c/tdef/AudioStreamBasicDescription).
+
+use error::Error;
+use super::audio_format::AudioFormat;
+use super::StreamFormat;
+use sys;
+
+/// A description of a stream of audio data in any **AudioFormat**.
+///
+/// > A *packet* is a collection of one or more contiguous frames. In linear PCM audio, a packet is
+/// always a single frame. In compressed formats, it is typically more.
+///
+/// [from *Core Audio Overview*](https://developer.apple.com/library/ios/documentation/MusicAudio/Conceptual/CoreAudioOverview/WhatisCoreAudio/WhatisCoreAudio.html)
+#[derive(Clone, Debug)]
+pub struct AudioStreamDescription {
+    /// The number of frames of audio data per second used to represent a signal.
+    pub sample_rate: f64,
+    /// The format of the audio data, along with its format-specific flags.
+    pub format: AudioFormat,
+    /// The number of bytes in each packet of audio data.
+    ///
+    /// `None` indicates that packets vary in size, in which case the size of each packet is
+    /// described by the `packet_descriptions`.
+    pub bytes_per_packet: Option<u32>,
+    /// The number of frames in each packet of audio data.
+    ///
+    /// This is `1` for uncompressed formats. `0` indicates that the number of frames varies
+    /// between packets.
+    pub frames_per_packet: u32,
+    /// The number of bytes from the start of one frame to the start of the next.
+    ///
+    /// This is `0` for compressed formats.
+    pub bytes_per_frame: u32,
+    /// The number of channels in each frame of audio data.
+    pub channels_per_frame: u32,
+    /// The number of bits for each channel in a frame.
+    ///
+    /// This is `0` for compressed formats.
+    pub bits_per_channel: u32,
+    /// Descriptions of each packet within a buffer of variable bit-rate or variable frames per
+    /// packet data.
+    pub packet_descriptions: Option<Vec<PacketDescription>>,
+    /// The format-specific "magic cookie" required by some codecs to decode the data.
+    pub magic_cookie: Option<Vec<u8>>,
+}
+
+/// Describes a single packet within a buffer of data where the size of each packet or the number
+/// of frames within each packet may vary.
+///
+/// A rustification of the `AudioStreamPacketDescription` type.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketDescription {
+    /// The number of bytes from the start of the buffer to the start of the packet.
+    pub start_offset: i64,
+    /// The number of frames in the packet, or `0` if the stream has a constant number of frames
+    /// per packet.
+    pub variable_frames_in_packet: u32,
+    /// The number of bytes in the packet.
+    pub data_byte_size: u32,
+}
+
+impl AudioStreamDescription {
+
+    /// Convert an AudioStreamBasicDescription into an AudioStreamDescription.
+    ///
+    /// The resulting description has no packet descriptions or magic cookie, as these are not
+    /// part of the ASBD.
+    pub fn from_asbd(asbd: sys::AudioStreamBasicDescription) -> Result<Self, Error> {
+        match AudioFormat::from_format_and_flag(asbd.mFormatID, Some(asbd.mFormatFlags)) {
+            Ok(format) => Ok(AudioStreamDescription::from_asbd_and_format(asbd, format)),
+            Err(err) => Err(Error::AudioFormat(err)),
+        }
+    }
+
+    // Construct the description from the given ASBD and its already decoded format.
+    #[allow(non_snake_case)]
+    fn from_asbd_and_format(asbd: sys::AudioStreamBasicDescription, format: AudioFormat) -> Self {
+        let sys::AudioStreamBasicDescription {
+            mSampleRate,
+            mBytesPerPacket,
+            mFramesPerPacket,
+            mBytesPerFrame,
+            mChannelsPerFrame,
+            mBitsPerChannel,
+            ..
+        } = asbd;
+
+        AudioStreamDescription {
+            sample_rate: mSampleRate,
+            format: format,
+            bytes_per_packet: if mBytesPerPacket == 0 { None } else { Some(mBytesPerPacket) },
+            frames_per_packet: mFramesPerPacket,
+            bytes_per_frame: mBytesPerFrame,
+            channels_per_frame: mChannelsPerFrame,
+            bits_per_channel: mBitsPerChannel,
+            packet_descriptions: None,
+            magic_cookie: None,
+        }
+    }
+
+    /// Convert an AudioStreamDescription into an AudioStreamBasicDescription.
+    pub fn to_asbd(&self) -> sys::AudioStreamBasicDescription {
+        let (format, maybe_flag) = self.format.to_format_and_flag();
+        sys::AudioStreamBasicDescription {
+            mSampleRate: self.sample_rate,
+            mFormatID: format,
+            mFormatFlags: maybe_flag.unwrap_or(0),
+            mBytesPerPacket: self.bytes_per_packet.unwrap_or(0),
+            mFramesPerPacket: self.frames_per_packet,
+            mBytesPerFrame: self.bytes_per_frame,
+            mChannelsPerFrame: self.channels_per_frame,
+            mBitsPerChannel: self.bits_per_channel,
+            mReserved: 0,
+        }
+    }
+
+    /// Whether or not the size of each packet varies, in which case `packet_descriptions` are
+    /// required to locate each packet within a buffer.
+    pub fn is_variable_bytes_per_packet(&self) -> bool {
+        self.bytes_per_packet.is_none()
+    }
+
+    /// Attempt to convert the description into a **StreamFormat** for use with the audio unit API.
+    ///
+    /// Returns an `Error` if the description does not describe `LinearPCM` data in a sample format
+    /// supported by **StreamFormat**.
+    pub fn try_into_stream_format(self) -> Result<StreamFormat, Error> {
+        StreamFormat::from_asbd(self.to_asbd())
+    }
+
+}
+
+impl From<StreamFormat> for AudioStreamDescription {
+    fn from(format: StreamFormat) -> Self {
+        // Decoding a format with flags never fails, so the fallback is never used.
+        let asbd = format.to_asbd();
+        let (id, flags) = (asbd.mFormatID, asbd.mFormatFlags);
+        let format = AudioFormat::from_format_and_flag(id, Some(flags))
+            .unwrap_or(AudioFormat::Other(id, flags));
+        AudioStreamDescription::from_asbd_and_format(asbd, format)
+    }
+}
+
+impl PacketDescription {
+
+    /// Convert from the `AudioStreamPacketDescription` FFI type.
+    #[allow(non_snake_case)]
+    pub fn from_sys(desc: sys::AudioStreamPacketDescription) -> Self {
+        let sys::AudioStreamPacketDescription {
+            mStartOffset,
+            mVariableFramesInPacket,
+            mDataByteSize,
+        } = desc;
+        PacketDescription {
+            start_offset: mStartOffset,
+            variable_frames_in_packet: mVariableFramesInPacket,
+            data_byte_size: mDataByteSize,
+        }
+    }
+
+    /// Convert to the `AudioStreamPacketDescription` FFI type.
+    pub fn to_sys(&self) -> sys::AudioStreamPacketDescription {
+        sys::AudioStreamPacketDescription {
+            mStartOffset: self.start_offset,
+            mVariableFramesInPacket: self.variable_frames_in_packet,
+            mDataByteSize: self.data_byte_size,
+        }
+    }
+
+}
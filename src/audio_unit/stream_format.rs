@@ -3,7 +3,7 @@
 //! Find the original `AudioStreamBasicDescription` reference [here](https://developer.apple.com/library/mac/documentation/MusicAudio/Reference/CoreAudioDataTypesRef/#//apple_ref/c/tdef/AudioStreamBasicDescription).
 
 use error::{self, Error};
-use super::audio_format::AudioFormat;
+use super::audio_format::{AudioFormat, LinearPcmFlags};
 use super::SampleFormat;
 use sys;
 
@@ -13,13 +13,22 @@ use sys;
 /// that is normally associated with the AudioStreamBasicDescription.
 ///
 /// Seeing as `LinearPCM` data (the `AudioFormat` used by the `AudioUnit` API) implies a single
-/// frame per packet, we can infer many of the fields in an ASBD from the sample type.
+/// frame per packet, we can infer many of the fields in an ASBD from the sample type, the number
+/// of channels and the `Layout`.
 ///
-/// `bytes_per_packet` = size_of::<S>()
-/// `bytes_per_frame` = size_of::<S>()
+/// `bytes_per_packet` = `bytes_per_frame`
+/// `bytes_per_frame` = size_of::<S>() * channels_per_frame (**Interleaved**)
+/// `bytes_per_frame` = size_of::<S>() (**NonInterleaved**)
 /// `frames_per_packet` = 1
 /// `bits_per_channel` = size_of::<S>() * 8
 ///
+/// Where the samples are not packed, `size_of::<S>()` is replaced by the
+/// `unpacked_bytes_per_sample`.
+///
+/// Note that for non-interleaved data the ASBD describes each of the individual buffers (one per
+/// channel) rather than the whole frame, which is why `bytes_per_frame` does not depend on the
+/// number of channels.
+///
 /// > A *packet* is a collection of one or more contiguous frames. In linear PCM audio, a packet is
 /// always a single frame.
 ///
@@ -46,8 +55,58 @@ pub struct StreamFormat {
     /// battery drain when processing audio. iOS provides a Converter audio unit and inclues the
     /// interfaces from Audio Converter Services (TODO: look into exposing this).
     pub sample_format: SampleFormat,
-    pub flags: super::audio_format::LinearPcmFlags,
+    /// The `LinearPCM` flags describing the format.
+    ///
+    /// The `IS_NON_INTERLEAVED` flag is determined by the `layout` field and is ignored here.
+    pub flags: LinearPcmFlags,
+    /// Whether the channels of each frame are interleaved within a single buffer or whether each
+    /// channel is delivered in its own buffer.
+    pub layout: Layout,
+    /// The number of channels in each frame of audio data.
     pub channels_per_frame: u32,
+    /// The number of bytes occupied by each sample if the samples are not packed, e.g. `Some(4)`
+    /// for 24-bit samples aligned within 32 bits. The `IS_ALIGNED_HIGH` flag describes whether
+    /// the sample occupies the high or the low bits.
+    ///
+    /// `None` if the samples are packed, in which case each occupies
+    /// `sample_format.size_in_bytes()` bytes.
+    pub unpacked_bytes_per_sample: Option<u32>,
+}
+
+/// The way in which the samples of each channel are laid out in memory.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// The samples for each frame are laid out contiguously and the frames are laid out end to
+    /// end within a single buffer.
+    ///
+    /// E.g. `[L, R, L, R, L, R, ...]`.
+    Interleaved,
+    /// The samples for each channel are laid out contiguously within their own buffer.
+    ///
+    /// E.g. `[L, L, L, ...]`, `[R, R, R, ...]`.
+    NonInterleaved,
+}
+
+impl Layout {
+
+    /// Determine the `Layout` from the `IS_NON_INTERLEAVED` flag.
+    pub fn from_flags(flags: LinearPcmFlags) -> Self {
+        if flags.contains(LinearPcmFlags::IS_NON_INTERLEAVED) {
+            Layout::NonInterleaved
+        } else {
+            Layout::Interleaved
+        }
+    }
+
+    /// The number of bytes in a single frame of a buffer with this `Layout`.
+    pub fn bytes_per_frame(&self, sample_format: SampleFormat, channels_per_frame: u32) -> u32 {
+        let sample_bytes = sample_format.size_in_bytes() as u32;
+        match *self {
+            Layout::Interleaved => sample_bytes * channels_per_frame,
+            Layout::NonInterleaved => sample_bytes,
+        }
+    }
+
 }
 
 impl StreamFormat {
@@ -64,7 +123,11 @@ impl StreamFormat {
     ///
     /// Returns an `Error` if the `AudioFormat` inferred by the ASBD is not `LinearPCM`.
     ///
-    /// Returns an `Error` if the sample format type kkkkkkkkkkkkkkkkkkkkkk
+    /// Returns an `Error` if the sample format cannot be determined from the flags and the
+    /// `mBitsPerChannel` field, or if the `mBytesPerFrame` and `mBytesPerPacket` fields do not
+    /// describe a single frame per packet. Unless the `IS_PACKED` flag is set, the samples may
+    /// occupy more bytes than the sample format requires, e.g. 24-bit samples aligned within 32
+    /// bits.
     #[allow(non_snake_case)]
     pub fn from_asbd(asbd: sys::AudioStreamBasicDescription) -> Result<StreamFormat, Error> {
         const NOT_SUPPORTED: Error = Error::AudioUnit(error::audio_unit::Error::FormatNotSupported);
@@ -73,48 +136,100 @@ impl StreamFormat {
             mSampleRate,
             mFormatID,
             mFormatFlags,
+            mBytesPerPacket,
+            mFramesPerPacket,
             mBytesPerFrame,
             mChannelsPerFrame,
+            mBitsPerChannel,
             ..
         } = asbd;
 
         // Retrieve the LinearPCM flags.
         let flags = match AudioFormat::from_format_and_flag(mFormatID, Some(mFormatFlags)) {
-            Some(AudioFormat::LinearPCM(flags)) => flags,
+            Ok(AudioFormat::LinearPCM(flags)) => flags,
             _ => return Err(NOT_SUPPORTED),
         };
 
         // Determine the `SampleFormat` to use.
-        let sample_format = match SampleFormat::from_flags_and_bytes_per_frame(flags, mBytesPerFrame) {
+        let sample_format = match SampleFormat::from_flags_and_bits_per_channel(flags, mBitsPerChannel) {
             Some(sample_format) => sample_format,
             None => return Err(NOT_SUPPORTED),
         };
 
+        // Ensure the byte sizes agree with the layout described by the flags.
+        let layout = Layout::from_flags(flags);
+        if mFramesPerPacket != 1 || mBytesPerPacket != mBytesPerFrame {
+            return Err(NOT_SUPPORTED);
+        }
+
+        // Samples that are not packed may occupy more bytes than the sample format requires.
+        let packed_bytes_per_frame = layout.bytes_per_frame(sample_format, mChannelsPerFrame);
+        let samples_per_frame = match layout {
+            Layout::Interleaved => mChannelsPerFrame,
+            Layout::NonInterleaved => 1,
+        };
+        let unpacked_bytes_per_sample = if mBytesPerFrame == packed_bytes_per_frame {
+            None
+        } else if !flags.contains(LinearPcmFlags::IS_PACKED)
+            && samples_per_frame > 0
+            && mBytesPerFrame > packed_bytes_per_frame
+            && mBytesPerFrame % samples_per_frame == 0
+        {
+            Some(mBytesPerFrame / samples_per_frame)
+        } else {
+            return Err(NOT_SUPPORTED);
+        };
+
         Ok(StreamFormat {
             sample_rate: mSampleRate,
-            flags: flags,
+            flags: flags - LinearPcmFlags::IS_NON_INTERLEAVED,
+            layout: layout,
             sample_format: sample_format,
             channels_per_frame: mChannelsPerFrame,
+            unpacked_bytes_per_sample: unpacked_bytes_per_sample,
         })
     }
 
+    /// The number of bytes occupied by each sample.
+    pub fn bytes_per_sample(&self) -> u32 {
+        match self.unpacked_bytes_per_sample {
+            Some(bytes) => bytes,
+            None => self.sample_format.size_in_bytes() as u32,
+        }
+    }
+
+    /// The number of bytes in a single frame of each of the buffers described by the format.
+    pub fn bytes_per_frame(&self) -> u32 {
+        match self.layout {
+            Layout::Interleaved => self.bytes_per_sample() * self.channels_per_frame,
+            Layout::NonInterleaved => self.bytes_per_sample(),
+        }
+    }
+
     /// Convert a StreamFormat into an AudioStreamBasicDescription.
     pub fn to_asbd(self) -> sys::AudioStreamBasicDescription {
+        let bytes_per_frame = self.bytes_per_frame();
         let StreamFormat {
             sample_rate,
             flags,
+            layout,
             sample_format,
             channels_per_frame,
+            ..
         } = self;
 
+        let flags = match layout {
+            Layout::Interleaved => flags - LinearPcmFlags::IS_NON_INTERLEAVED,
+            Layout::NonInterleaved => flags | LinearPcmFlags::IS_NON_INTERLEAVED,
+        };
+
         let (format, maybe_flag) = AudioFormat::LinearPCM(flags).to_format_and_flag();
 
         let flag = maybe_flag.unwrap_or(::std::u32::MAX -2147483647);
 
-        let bytes_per_frame = sample_format.size_in_bytes() as u32;
         const FRAMES_PER_PACKET: u32 = 1;
         let bytes_per_packet = bytes_per_frame * FRAMES_PER_PACKET;
-        let bits_per_channel = bytes_per_frame * 8;
+        let bits_per_channel = sample_format.size_in_bytes() as u32 * 8;
 
         sys::AudioStreamBasicDescription {
             mSampleRate: sample_rate,
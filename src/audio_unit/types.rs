@@ -2,11 +2,30 @@
 //!
 //! Oirginal documentation [here](https://developer.apple.com/library/prerelease/mac/documentation/AudioUnit/Reference/AUComponentServicesReference/index.html#//apple_ref/doc/constant_group/Audio_Unit_Types).
 
+use four_cc::FourCC;
+use std::fmt;
+
+
+// The component type code of each `Type`.
+mod id {
+    use four_cc::FourCC;
+    pub const IO:               u32 = FourCC::from_bytes(b"auou").0;
+    pub const MUSIC_DEVICE:     u32 = FourCC::from_bytes(b"aumu").0;
+    pub const MUSIC_EFFECT:     u32 = FourCC::from_bytes(b"aumf").0;
+    pub const FORMAT_CONVERTER: u32 = FourCC::from_bytes(b"aufc").0;
+    pub const EFFECT:           u32 = FourCC::from_bytes(b"aufx").0;
+    pub const MIXER:            u32 = FourCC::from_bytes(b"aumx").0;
+    pub const PANNER:           u32 = FourCC::from_bytes(b"aupn").0;
+    pub const GENERATOR:        u32 = FourCC::from_bytes(b"augn").0;
+    pub const OFFLINE_EFFECT:   u32 = FourCC::from_bytes(b"auol").0;
+    pub const MIDI_PROCESSOR:   u32 = FourCC::from_bytes(b"aumi").0;
+}
+
 
 /// Represents the different kinds of Audio Units that are available.
 ///
 /// Original documentation [here](https://developer.apple.com/library/prerelease/mac/documentation/AudioUnit/Reference/AUComponentServicesReference/index.html#//apple_ref/doc/constant_group/Audio_Unit_Types).
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Type {
     /// Provides input, output, or both input and output simultaneously.
     ///
@@ -25,8 +44,11 @@ pub enum Type {
     /// An effect unit that can respond to MIDI control messages, typically through a mapping of
     /// MIDI messages to parameters of the audio unit's DSP algorithm.
     ///
+    /// Apple does not supply any music effect units, so the subtype of a third-party unit is held
+    /// by `MusicEffectType::Unknown`.
+    ///
     /// **Available** in OS X v10.2 and later.
-    MusicEffect,
+    MusicEffect(MusicEffectType),
     /// A format converter unit can transform audio formats, such as performing sample rate
     /// conversion.
     ///
@@ -70,7 +92,7 @@ pub enum Type {
     /// coordinates.
     ///
     /// **Available** in OS X v10.3 and later.
-    Panner,
+    Panner(PannerType),
     /// A generator unit provides audio output that has no audio input.
     ///
     /// This audio unit type is appropriate for a tone generator.
@@ -89,10 +111,18 @@ pub enum Type {
     /// before the actual rendering/processing phase is executed.
     ///
     /// **Available** in OS X v10.3 and later.
-    OfflineEffect,
+    OfflineEffect(OfflineEffectType),
     /// FIXME: Could not find any documenation for this type - it seems it was added very recently
     /// (around 2013) and Apple's documentation doesn't seem to have updated to include it.
-    MidiProcessor,
+    ///
+    /// Apple does not supply any MIDI processor units, so the subtype of a third-party unit is held
+    /// by `MidiProcessorType::Unknown`.
+    MidiProcessor(MidiProcessorType),
+    /// A type and subtype pair that is not known to coreaudio-rs, e.g. a third-party audio unit
+    /// or one added in a newer version of the OS.
+    ///
+    /// Holds the component type followed by the component subtype.
+    Unknown(FourCC, FourCC),
 }
 
 
@@ -101,19 +131,56 @@ impl Type {
     /// Convert the `Type` to its associated `u32` for compatibility with original API.
     pub fn to_u32(&self) -> u32 {
         match *self {
-            Type::IO(_)              => 1635086197,
-            Type::MusicDevice(_)     => 1635085685,
-            Type::MusicEffect        => 1635085670,
-            Type::FormatConverter(_) => 1635083875,
-            Type::Effect(_)          => 1635083896,
-            Type::Mixer(_)           => 1635085688,
-            Type::Panner             => 1635086446,
-            Type::Generator(_)       => 1635084142,
-            Type::OfflineEffect      => 1635086188,
-            Type::MidiProcessor      => 1635085673,
+            Type::IO(_)              => id::IO,
+            Type::MusicDevice(_)     => id::MUSIC_DEVICE,
+            Type::MusicEffect(_)     => id::MUSIC_EFFECT,
+            Type::FormatConverter(_) => id::FORMAT_CONVERTER,
+            Type::Effect(_)          => id::EFFECT,
+            Type::Mixer(_)           => id::MIXER,
+            Type::Panner(_)          => id::PANNER,
+            Type::Generator(_)       => id::GENERATOR,
+            Type::OfflineEffect(_)   => id::OFFLINE_EFFECT,
+            Type::MidiProcessor(_)   => id::MIDI_PROCESSOR,
+            Type::Unknown(ty, _)     => ty.0,
         }
     }
 
+    /// Convert a component type and subtype `u32` pair (as found in an
+    /// `AudioComponentDescription`) back into a `Type`.
+    ///
+    /// Any pair that is not known to coreaudio-rs is returned as `Type::Unknown`, except for the
+    /// `MusicEffect` and `MidiProcessor` types whose subtypes are always held by their `Unknown`
+    /// subtype variants.
+    pub fn from_u32(ty: u32, subtype: u32) -> Type {
+        let unknown = Type::Unknown(FourCC(ty), FourCC(subtype));
+        let known = match ty {
+            id::IO                              => IOType::from_u32(subtype).map(Type::IO),
+            id::MUSIC_DEVICE                    => {
+                MusicDeviceType::from_u32(subtype).map(Type::MusicDevice)
+            },
+            id::FORMAT_CONVERTER                => {
+                FormatConverterType::from_u32(subtype).map(Type::FormatConverter)
+            },
+            id::EFFECT                          => EffectType::from_u32(subtype).map(Type::Effect),
+            id::MIXER                           => MixerType::from_u32(subtype).map(Type::Mixer),
+            id::GENERATOR                       => {
+                GeneratorType::from_u32(subtype).map(Type::Generator)
+            },
+            id::PANNER                          => PannerType::from_u32(subtype).map(Type::Panner),
+            id::OFFLINE_EFFECT                  => {
+                OfflineEffectType::from_u32(subtype).map(Type::OfflineEffect)
+            },
+            id::MUSIC_EFFECT                    => {
+                MusicEffectType::from_u32(subtype).map(Type::MusicEffect)
+            },
+            id::MIDI_PROCESSOR                  => {
+                MidiProcessorType::from_u32(subtype).map(Type::MidiProcessor)
+            },
+            _                                   => None,
+        };
+        known.unwrap_or(unknown)
+    }
+
     /// Convert the `Type` to the const `u32` that is associated with its subtype.
     pub fn to_subtype_u32(&self) -> Option<u32> {
         match *self {
@@ -123,10 +190,142 @@ impl Type {
             Type::Effect(ty)          => Some(ty as u32),
             Type::Mixer(ty)           => Some(ty as u32),
             Type::Generator(ty)       => Some(ty as u32),
-            _ => None,
+            Type::Panner(ty)          => Some(ty as u32),
+            Type::OfflineEffect(ty)   => Some(ty as u32),
+            Type::MusicEffect(ty)     => Some(ty.to_u32()),
+            Type::MidiProcessor(ty)   => Some(ty.to_u32()),
+            Type::Unknown(_, ty)      => Some(ty.0),
+        }
+    }
+
+    /// Whether or not the `Type` is an I/O unit, which may be started and stopped.
+    pub fn is_io(&self) -> bool {
+        self.to_u32() == id::IO
+    }
+
+    /// The four-character code of the `Type`, e.g. `'aufx'`.
+    pub fn four_cc(&self) -> FourCC {
+        FourCC(self.to_u32())
+    }
+
+    /// The four-character code of the `Type`'s subtype (if it has one), e.g. `'lmtr'`.
+    pub fn subtype_four_cc(&self) -> Option<FourCC> {
+        self.to_subtype_u32().map(FourCC)
+    }
+
+}
+
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.subtype_four_cc() {
+            Some(subtype) => write!(f, "{}/{}", self.four_cc(), subtype),
+            None => write!(f, "{}", self.four_cc()),
         }
     }
+}
+
 
+/// Implements `from_u32`, `four_cc` and `Display` (rendered as the four-character code) for a
+/// subtype enum with the given variants.
+macro_rules! impl_subtype {
+    ($($T:ident { $($V:ident),* $(,)* })*) => {
+        $(
+            impl $T {
+                /// Convert a subtype `u32` back into the enum, if it is a known subtype.
+                pub fn from_u32(u: u32) -> Option<$T> {
+                    $(
+                        if u == $T::$V as u32 {
+                            return Some($T::$V);
+                        }
+                    )*
+                    None
+                }
+
+                /// The four-character code of the subtype.
+                pub fn four_cc(&self) -> FourCC {
+                    FourCC(*self as u32)
+                }
+            }
+
+            impl fmt::Display for $T {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Display::fmt(&self.four_cc(), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_subtype! {
+    EffectType {
+        PeakLimiter, DynamicsProcessor, LowPassFilter, HighPassFilter, BandPassFilter,
+        HighShelfFilter, LowShelfFilter, ParametricEQ, Distortion, Delay, SampleDelay, GraphicEQ,
+        MultiBandCompressor, MatrixReverb, Pitch, AUFilter, NetSend, RogerBeep, NBandEQ,
+    }
+    FormatConverterType {
+        AUConverter, NewTimePitch, TimePitch, DeferredRenderer, Splitter, Merger, Varispeed,
+        AUiPodTimeOther, RoundTripAAC,
+    }
+    MixerType {
+        MultiChannelMixer, StereoMixer, Mixer3D, MatrixMixer, SpatialMixer,
+    }
+    GeneratorType {
+        ScheduledSoundPlayer, AudioFilePlayer, NetReceive,
+    }
+    MusicDeviceType {
+        DLSSynth, Sampler, MIDISynth,
+    }
+    IOType {
+        GenericOutput, HalOutput, DefaultOutput, SystemOutput, VoiceProcessingIO, RemoteIO,
+    }
+    PannerType {
+        SphericalHeadPanner, VectorPanner, SoundFieldPanner, HRTFPanner,
+    }
+    OfflineEffectType {
+        TimePitch, NewTimePitch, Varispeed,
+    }
+}
+
+
+/// Implements `from_u32`, `to_u32`, `four_cc` and `Display` for a subtype enum whose subtypes are
+/// all held by its `Unknown` variant.
+macro_rules! impl_unknown_subtype {
+    ($($T:ident)*) => {
+        $(
+            impl $T {
+                /// Convert a subtype `u32` into the enum.
+                ///
+                /// This always succeeds, as every subtype is held by the `Unknown` variant.
+                pub fn from_u32(u: u32) -> Option<$T> {
+                    Some($T::Unknown(FourCC(u)))
+                }
+
+                /// Convert the subtype to its associated `u32`.
+                pub fn to_u32(&self) -> u32 {
+                    match *self {
+                        $T::Unknown(subtype) => subtype.0,
+                    }
+                }
+
+                /// The four-character code of the subtype.
+                pub fn four_cc(&self) -> FourCC {
+                    FourCC(self.to_u32())
+                }
+            }
+
+            impl fmt::Display for $T {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Display::fmt(&self.four_cc(), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_unknown_subtype! {
+    MusicEffectType
+    MidiProcessorType
 }
 
 
@@ -166,107 +365,133 @@ impl From<IOType> for Type {
     }
 }
 
+impl From<PannerType> for Type {
+    fn from(ty: PannerType) -> Self {
+        Type::Panner(ty)
+    }
+}
+
+impl From<OfflineEffectType> for Type {
+    fn from(ty: OfflineEffectType) -> Self {
+        Type::OfflineEffect(ty)
+    }
+}
+
+impl From<MusicEffectType> for Type {
+    fn from(ty: MusicEffectType) -> Self {
+        Type::MusicEffect(ty)
+    }
+}
+
+impl From<MidiProcessorType> for Type {
+    fn from(ty: MidiProcessorType) -> Self {
+        Type::MidiProcessor(ty)
+    }
+}
+
 
 /// Effect (digital signal processing) audio unit subtypes for audio units provided by Apple.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum EffectType {
     /// An audio unit that enforces an upper dynamic limit on an audio signal.
     ///
     /// **Available** in OS X v10.2 and later.
-    PeakLimiter = 1819112562,
+    PeakLimiter = FourCC::from_bytes(b"lmtr").0,
     /// An audio unit that provides dynamic compression or expansion.
     ///
     /// **Available** in OS X v10.3 and later.
-    DynamicsProcessor = 1684237680,
+    DynamicsProcessor = FourCC::from_bytes(b"dcmp").0,
     /// An audio unit that passes frequencies below a specified cutoff frequency and blocks
     /// frequencies above that cutoff frequency.
     ///
     /// **Available** in OS X v10.2 and later.
-    LowPassFilter = 1819304307,
+    LowPassFilter = FourCC::from_bytes(b"lpas").0,
     /// An audio unit that passes frequencies above a specified cutoff frequency and blocks
     /// frequencies below that cutoff frequency.
     ///
     /// **Available** in OS X v10.2 and later.
-    HighPassFilter = 1752195443,
+    HighPassFilter = FourCC::from_bytes(b"hpas").0,
     /// An audio unit that passes frequencies between specified upper and lower cutoff frequencies,
     /// and blocks frequencies outside that band.
     ///
     /// **Available** in OS X v10.2 and later.
-    BandPassFilter = 1651532147,
+    BandPassFilter = FourCC::from_bytes(b"bpas").0,
     /// An audio unit suitable for implementing a treble control in an audio playback or recording
     /// system.
     ///
     /// **Available** in OS X v10.2 and later.
-    HighShelfFilter = 1752393830,
+    HighShelfFilter = FourCC::from_bytes(b"hshf").0,
     /// An audio unit suitable for implementing a bass control in an audio playback or recording
     /// system.
     ///
     /// **Available** in OS X v10.2 and later.
-    LowShelfFilter = 1819502694,
+    LowShelfFilter = FourCC::from_bytes(b"lshf").0,
     /// An audio unit that provides a filter whose center frequency, boost/cut level, and Q can be
     /// adjusted.
     ///
     /// **Available** in OS X v10.2 and later.
-    ParametricEQ = 1886217585,
+    ParametricEQ = FourCC::from_bytes(b"pmeq").0,
     /// An audio unit that provides a distortion effect.
     ///
     /// **Available** in OS X v10.5 and later.
-    Distortion = 1684632436,
+    Distortion = FourCC::from_bytes(b"dist").0,
     /// An audio unit that introduces a time delay to a signal.
     ///
     /// **Available** in OS X v10.2 and later.
-    Delay = 1684368505,
+    Delay = FourCC::from_bytes(b"dely").0,
     /// An audio unit that provides a time delay for a specified number of samples.
     ///
     /// **Available** in OS X v10.4 and later.
-    SampleDelay = 1935961209,
+    SampleDelay = FourCC::from_bytes(b"sdly").0,
     /// An audio unit that provides a 10- or 31-band graphic equalizer.
     ///
     /// Available in OS X v10.2 and later.
-    GraphicEQ = 1735550321,
+    GraphicEQ = FourCC::from_bytes(b"greq").0,
     /// An audio unit that provides four-bands of dynamic compression or expansion.
     ///
     /// **Available** in OS X v10.3 and later.
-    MultiBandCompressor = 1835232624,
+    MultiBandCompressor = FourCC::from_bytes(b"mcmp").0,
     /// An audio unit that provides a reverberation effect that can be used to simulate a variety
     /// of acoustic spaces.
     ///
     /// **Available** in OS X v10.2 and later.
-    MatrixReverb = 1836213622,
+    MatrixReverb = FourCC::from_bytes(b"mrev").0,
     /// An audio unit for modifying the pitch of a signal.
     ///
     /// **Available** in OS X v10.4 and later.
-    Pitch = 1953329268,
+    Pitch = FourCC::from_bytes(b"tmpt").0,
     /// An audio unit that provides a combination of five filters: low-frequency, three
     /// mid-frequencies, and high-frequency.
     ///
     /// **Available** in OS X v10.4 and later.
-    AUFilter = 1718185076,
-    /// An audio unit for use in conjunction with a kAudioUnitSubType_NetReceive audio unit for
+    AUFilter = FourCC::from_bytes(b"filt").0,
+    /// An audio unit for use in conjunction with a **GeneratorType::NetReceive** audio unit for
     /// sending audio across a network or from one application to another.
     ///
     /// **Available** in OS X v10.4 and later.
-    NetSend = 1853058660,
+    NetSend = FourCC::from_bytes(b"nsnd").0,
     /// An audio unit that detects gaps between segments of speech and fills the gaps with a short
     /// tone, simulating the sound of a walkie-talkie communication device.
     ///
     /// **Available** in OS X v10.5 and later.
-    RogerBeep = 1919903602,
+    RogerBeep = FourCC::from_bytes(b"rogr").0,
     /// A multi-band equalizer with specifiable filter type for each band.
     ///
     /// **Available** in OS X v10.9 and later.
-    NBandEQ = 1851942257,
+    NBandEQ = FourCC::from_bytes(b"nbeq").0,
 }
 
 
 /// Audio data format converter audio unit subtypes for **AudioUnit**s provided by Apple.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum FormatConverterType {
     /// An audio unit that uses an audio converter to do linear PCM conversions, such as changes to
     /// sample rate, bit depth, or interleaving.
     ///
     /// **Available** in OS X v10.2 and later.
-    AUConverter = 1668247158,
+    AUConverter = FourCC::from_bytes(b"conv").0,
     /// An audio unit that can be used to have independent control of both playback rate and pitch.
     ///
     /// In OS X it provides a generic view, so it can be used in both a UI and programmatic
@@ -275,13 +500,13 @@ pub enum FormatConverterType {
     /// It also comes in an offline version for processing audio files.
     ///
     /// **Available** in OS X v10.7 and later.
-    NewTimePitch = 1853191280,
+    NewTimePitch = FourCC::from_bytes(b"nutp").0,
     /// An audio unit that can provide independent control of playback rate and pitch. This subtype
     /// provides a generic view, making it suitable for UI and programmatic context. OS X provides
     /// realtime and offline audio units of this subtype.
     ///
     /// **Available** in OS X v10.3 and later.
-    TimePitch = 1953329268,
+    TimePitch = FourCC::from_bytes(b"tmpt").0,
     /// An audio unit that acquires audio input from a separate thread than the thread on which its
     /// render method is called.
     ///
@@ -290,17 +515,17 @@ pub enum FormatConverterType {
     /// There is a delay, equal to the buffer size, introduced between the audio input and output.
     ///
     /// **Available** in OS X v10.4 and later.
-    DeferredRenderer = 1684366962,
+    DeferredRenderer = FourCC::from_bytes(b"defr").0,
     /// An audio unit with one input bus and two output buses. The audio unit duplicates the input
     /// signal to each of its two output buses.
     ///
     /// **Available** in OS X v10.4 and later.
-    Splitter = 1936747636,
+    Splitter = FourCC::from_bytes(b"splt").0,
     /// An audio unit with two input buses and one output bus. The audio unit merges the two input
     /// signals to the single output.
     ///
     /// **Available** in OS X v10.4 and later.
-    Merger = 1835364967,
+    Merger = FourCC::from_bytes(b"merg").0,
     /// An audio unit that can control playback rate. As the playback rate increases, so does
     /// pitch.
     /// 
@@ -309,14 +534,18 @@ pub enum FormatConverterType {
     /// OS X provides realtime and offline audio units of this subtype.
     ///
     /// **Available** in OS X v10.3 and later.
-    Varispeed = 1986097769,
+    Varispeed = FourCC::from_bytes(b"vari").0,
     /// **Available** in OS X v10.9 and later.
-    AUiPodTimeOther = 1768977519,
+    AUiPodTimeOther = FourCC::from_bytes(b"ipto").0,
+    /// An audio unit that encodes its input to AAC and then decodes it again, so that the effect
+    /// of AAC compression on a signal can be auditioned in real-time.
+    RoundTripAAC = FourCC::from_bytes(b"raac").0,
 }
 
 
 /// Audio mixing **AudioUnit** subtypes for **AudioUnit**s provided by Apple.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum MixerType {
     /// An audio unit that can have any number of input buses, with any number of channels on each
     /// input bus, and one output bus.
@@ -326,12 +555,12 @@ pub enum MixerType {
     /// In iPhone OS, the output bus always has two channels.
     ///
     /// **Available** in OS X v10.5 and later.
-    MultiChannelMixer = 1835232632,
+    MultiChannelMixer = FourCC::from_bytes(b"mcmx").0,
     /// An audio unit that can have any number of input buses, each of which is mono or stereo, and
     /// one stereo output bus.
     ///
     /// **Available** in OS X v10.2 and later.
-    StereoMixer = 1936554098,
+    StereoMixer = FourCC::from_bytes(b"smxr").0,
     /// An audio unit that can have any number of input buses and one output bus.
     ///
     /// Each input bus can be mono, in which case it can be panned using 3D coordinates and
@@ -346,7 +575,7 @@ pub enum MixerType {
     /// **Available** in OS X v10.3 and later.
     /// 
     /// **Deprecated** in OS X v10.10.
-    Mixer3D = 862219640,
+    Mixer3D = FourCC::from_bytes(b"3dmx").0,
     /// An audio unit that can have any number of input and output buses with any number of
     /// channels on each bus.
     ///
@@ -360,12 +589,18 @@ pub enum MixerType {
     /// Finally, the audio unit provides a global level control for the matrix as a whole.
     ///
     /// **Available** in OS X v10.3 and later.
-    MatrixMixer = 1836608888,
+    MatrixMixer = FourCC::from_bytes(b"mxmx").0,
+    /// An audio unit that can have any number of input buses and one output bus, spatializing
+    /// each input within a 3D environment.
+    ///
+    /// This is the successor to **Mixer3D**.
+    SpatialMixer = FourCC::from_bytes(b"3dem").0,
 }
 
 
 /// Audio units that serve as sound sources.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum GeneratorType {
     /// A generator unit that can be used to schedule slices of audio to be played at specified
     /// times.
@@ -374,17 +609,21 @@ pub enum GeneratorType {
     /// from any thread.
     ///
     /// **Available** in OS X v10.4 and later.
-    ScheduledSoundPlayer = 1936945260,
+    ScheduledSoundPlayer = FourCC::from_bytes(b"sspl").0,
     /// A generator unit that is used to play a file. In OS X it presents a custom UI so can be
     /// used in a UI context as well as in a programmatic context.
     ///
     /// **Available** in OS X v10.4 and later.
-    AudioFilePlayer = 1634103404,
+    AudioFilePlayer = FourCC::from_bytes(b"afpl").0,
+    /// A generator unit for use in conjunction with a **NetSend** effect unit that receives audio
+    /// sent across a network or from another application.
+    NetReceive = FourCC::from_bytes(b"nrcv").0,
 }
 
 
 /// Audio units that can be played as musical instruments via MIDI control.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum MusicDeviceType {
     /// A multitimbral instrument unit that can use sample banks in either DLS or SoundFont
     /// formats.
@@ -392,17 +631,21 @@ pub enum MusicDeviceType {
     /// It fully supports GM-MIDI and the basic extensions of GS-MIDI
     ///
     /// **Available** in OS X v10.2 and later.
-    DLSSynth = 1684828960,
+    DLSSynth = FourCC::from_bytes(b"dls ").0,
     /// A monotimbral instrument unit that functions a a sampler-synthesizer and supports full
     /// interactive editing of its state.
     ///
     /// **Available** in OS X v10.7 and later.
-    Sampler = 1935764848,
+    Sampler = FourCC::from_bytes(b"samp").0,
+    /// A fully GM-compatible multitimbral instrument unit that loads its instruments from
+    /// SoundFont or DLS sample banks, with a separate sampler voice per MIDI channel.
+    MIDISynth = FourCC::from_bytes(b"msyn").0,
 }
 
 
 /// Input/output **AudioUnit** subtypes for **AudioUnit**s provided by Apple.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
 pub enum IOType {
     /// An audio unit that responds to start/stop calls and provides basic services for converting
     /// to and from linear PCM formats.
@@ -418,23 +661,23 @@ pub enum IOType {
     /// a parent audio processing graph.
     ///
     /// **Available** in OS X v10.2 and later.
-    GenericOutput = 1734700658,
+    GenericOutput = FourCC::from_bytes(b"genr").0,
     /// An audio unit that can provides input/output connection to an a specified audio device.
     ///
     /// Bus 0 provides output to the audio device and bus 1 accepts input from the audio device.
     ///
     /// **Available** in OS X v10.2 and later.
-    HalOutput = 1634230636,
+    HalOutput = FourCC::from_bytes(b"ahal").0,
     /// A specialized **HalOutput** audio unit that connects to the user’s selected default device
     /// in Sound Preferences.
     ///
     /// **Available** in OS X v10.2 and later.
-    DefaultOutput = 1684366880,
+    DefaultOutput = FourCC::from_bytes(b"def ").0,
     /// A specialized **HalOutput** audio unit that connects to the user’s selected device for
     /// sound effects, alerts, and other user-interface sounds.
     ///
     /// **Available** in OS X v10.2 and later.
-    SystemOutput = 1937339168,
+    SystemOutput = FourCC::from_bytes(b"sys ").0,
     /// An audio unit that interfaces to the audio inputs and outputs of iPhone OS devices and
     /// provides voice processing features.
     ///
@@ -445,13 +688,67 @@ pub enum IOType {
     /// enumeration for the identifiers for this audio unit’s properties.
     ///
     /// **Available** in OS X v10.7 and later.
-    VoiceProcessingIO = 1987078511,
+    VoiceProcessingIO = FourCC::from_bytes(b"vpio").0,
     /// Connects to device hardware for input, output, or simultaneous input and output.
     /// Use it for playback, recording, or low-latency simultaneous input and output where echo
     /// cancelation is not needed.
     ///
     /// See https://developer.apple.com/library/content/documentation/MusicAudio/Conceptual/AudioUnitHostingGuide_iOS/UsingSpecificAudioUnits/UsingSpecificAudioUnits.html
     /// **Available** in iOS.
-    RemoteIO = 1919512419,
+    RemoteIO = FourCC::from_bytes(b"rioc").0,
 }
 
+
+/// Panner **AudioUnit** subtypes for **AudioUnit**s provided by Apple.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum PannerType {
+    /// A panner unit that simulates the placement of a sound source around a listener's head
+    /// using spherical head modelling.
+    SphericalHeadPanner = FourCC::from_bytes(b"sphr").0,
+    /// A panner unit that uses vector based amplitude panning across a speaker layout.
+    VectorPanner = FourCC::from_bytes(b"vbas").0,
+    /// A panner unit that pans using ambisonic sound field encoding and decoding.
+    SoundFieldPanner = FourCC::from_bytes(b"ambi").0,
+    /// A panner unit that uses head-related transfer functions to position a sound source for
+    /// headphone listening.
+    HRTFPanner = FourCC::from_bytes(b"hrtf").0,
+}
+
+
+/// Offline effect **AudioUnit** subtypes for **AudioUnit**s provided by Apple.
+///
+/// These are the offline versions of the equivalent **FormatConverterType**s, for processing
+/// audio files faster than real-time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum OfflineEffectType {
+    /// The offline version of **FormatConverterType::TimePitch**.
+    TimePitch = FourCC::from_bytes(b"tmpt").0,
+    /// The offline version of **FormatConverterType::NewTimePitch**.
+    NewTimePitch = FourCC::from_bytes(b"nutp").0,
+    /// The offline version of **FormatConverterType::Varispeed**.
+    Varispeed = FourCC::from_bytes(b"vari").0,
+}
+
+
+/// Music effect **AudioUnit** subtypes.
+///
+/// Apple does not supply any music effect units, so the subtypes of third-party units are held by
+/// `Unknown`. A subtype of `FourCC(0)` matches every music effect unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MusicEffectType {
+    /// A subtype that is not known to coreaudio-rs, e.g. that of a third-party unit.
+    Unknown(FourCC),
+}
+
+
+/// MIDI processor **AudioUnit** subtypes.
+///
+/// Apple does not supply any MIDI processor units, so the subtypes of third-party units are held
+/// by `Unknown`. A subtype of `FourCC(0)` matches every MIDI processor unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MidiProcessorType {
+    /// A subtype that is not known to coreaudio-rs, e.g. that of a third-party unit.
+    Unknown(FourCC),
+}
@@ -4,18 +4,22 @@ pub use self::audio::Error as AudioError;
 pub use self::audio_codec::Error as AudioCodecError;
 pub use self::audio_format::Error as AudioFormatError;
 pub use self::audio_unit::Error as AudioUnitError;
+use four_cc::FourCC;
 use sys::OSStatus;
 
 pub mod audio {
+    use four_cc::FourCC;
     use sys::OSStatus;
 
+    const BAD_FILE_PATH: OSStatus = FourCC::from_bytes(b"!pth").to_os_status();
+
     #[derive(Copy, Clone, Debug)]
     pub enum Error {
         Unimplemented    = -4,
         FileNotFound     = -43,
         FilePermission   = -54,
         TooManyFilesOpen = -42,
-        BadFilePath      = 561017960,
+        BadFilePath      = BAD_FILE_PATH as isize,
         Param            = -50,
         MemFull          = -108,
         Unknown,
@@ -25,20 +29,24 @@ pub mod audio {
 
         pub fn from_os_status(os_status: OSStatus) -> Result<(), Error> {
             match os_status {
-                0         => Ok(()),
-                -4        => Err(Error::Unimplemented),
-                -43       => Err(Error::FileNotFound),
-                -54       => Err(Error::FilePermission),
-                -42       => Err(Error::TooManyFilesOpen),
-                561017960 => Err(Error::BadFilePath),
-                -50       => Err(Error::Param),
-                -108      => Err(Error::MemFull),
-                _         => Err(Error::Unknown),
+                0             => Ok(()),
+                -4            => Err(Error::Unimplemented),
+                -43           => Err(Error::FileNotFound),
+                -54           => Err(Error::FilePermission),
+                -42           => Err(Error::TooManyFilesOpen),
+                BAD_FILE_PATH => Err(Error::BadFilePath),
+                -50           => Err(Error::Param),
+                -108          => Err(Error::MemFull),
+                _             => Err(Error::Unknown),
             }
         }
 
         pub fn to_os_status(&self) -> OSStatus {
-            *self as OSStatus
+            match *self {
+                // `Unknown` has no status code of its own.
+                Error::Unknown => -1500,
+                err => err as OSStatus,
+            }
         }
 
     }
@@ -68,17 +76,26 @@ pub mod audio {
 
 
 pub mod audio_codec {
+    use four_cc::FourCC;
     use sys::OSStatus;
 
+    const UNSPECIFIED: OSStatus = FourCC::from_bytes(b"what").to_os_status();
+    const UNKNOWN_PROPERTY: OSStatus = FourCC::from_bytes(b"who?").to_os_status();
+    const BAD_PROPERTY_SIZE: OSStatus = FourCC::from_bytes(b"!siz").to_os_status();
+    const ILLEGAL_OPERATION: OSStatus = FourCC::from_bytes(b"nope").to_os_status();
+    const UNSUPPORTED_FORMAT: OSStatus = FourCC::from_bytes(b"!dat").to_os_status();
+    const STATE: OSStatus = FourCC::from_bytes(b"!stt").to_os_status();
+    const NOT_ENOUGH_BUFFER_SPACE: OSStatus = FourCC::from_bytes(b"!buf").to_os_status();
+
     #[derive(Copy, Clone, Debug)]
     pub enum Error {
-        Unspecified          = 2003329396,
-        UnknownProperty      = 2003332927,
-        BadPropertySize      = 561211770,
-        IllegalOperation     = 1852797029,
-        UnsupportedFormat    = 560226676,
-        State                = 561214580,
-        NotEnoughBufferSpace = 560100710,
+        Unspecified          = UNSPECIFIED as isize,
+        UnknownProperty      = UNKNOWN_PROPERTY as isize,
+        BadPropertySize      = BAD_PROPERTY_SIZE as isize,
+        IllegalOperation     = ILLEGAL_OPERATION as isize,
+        UnsupportedFormat    = UNSUPPORTED_FORMAT as isize,
+        State                = STATE as isize,
+        NotEnoughBufferSpace = NOT_ENOUGH_BUFFER_SPACE as isize,
         Unknown,
     }
 
@@ -86,20 +103,24 @@ pub mod audio_codec {
 
         pub fn from_os_status(os_status: OSStatus) -> Result<(), Error> {
             match os_status {
-                0          => Ok(()),
-                2003329396 => Err(Error::Unspecified),
-                2003332927 => Err(Error::UnknownProperty),
-                561211770  => Err(Error::BadPropertySize),
-                1852797029 => Err(Error::IllegalOperation),
-                560226676  => Err(Error::UnsupportedFormat),
-                561214580  => Err(Error::State),
-                560100710  => Err(Error::NotEnoughBufferSpace),
-                _          => Err(Error::Unknown),
+                0                       => Ok(()),
+                UNSPECIFIED             => Err(Error::Unspecified),
+                UNKNOWN_PROPERTY        => Err(Error::UnknownProperty),
+                BAD_PROPERTY_SIZE       => Err(Error::BadPropertySize),
+                ILLEGAL_OPERATION       => Err(Error::IllegalOperation),
+                UNSUPPORTED_FORMAT      => Err(Error::UnsupportedFormat),
+                STATE                   => Err(Error::State),
+                NOT_ENOUGH_BUFFER_SPACE => Err(Error::NotEnoughBufferSpace),
+                _                       => Err(Error::Unknown),
             }
         }
 
         pub fn to_os_status(&self) -> OSStatus {
-            *self as OSStatus
+            match *self {
+                // `Unknown` has no status code of its own.
+                Error::Unknown => -1500,
+                err => err as OSStatus,
+            }
         }
 
     }
@@ -129,32 +150,48 @@ pub mod audio_codec {
 
 
 pub mod audio_format {
+    use four_cc::FourCC;
     use sys::OSStatus;
 
-    // TODO: Finish implementing these values.
+    const UNSPECIFIED: OSStatus = FourCC::from_bytes(b"what").to_os_status();
+    const UNSUPPORTED_PROPERTY: OSStatus = FourCC::from_bytes(b"prop").to_os_status();
+    const BAD_PROPERTY_SIZE: OSStatus = FourCC::from_bytes(b"!siz").to_os_status();
+    const BAD_SPECIFIER_SIZE: OSStatus = FourCC::from_bytes(b"!spc").to_os_status();
+    const UNSUPPORTED_DATA_FORMAT: OSStatus = FourCC::from_bytes(b"fmt?").to_os_status();
+    const UNKNOWN_FORMAT: OSStatus = FourCC::from_bytes(b"!fmt").to_os_status();
+
     #[derive(Copy, Clone, Debug)]
     pub enum Error {
-        Unspecified, // 'what'
-        UnsupportedProperty, // 'prop'
-        BadPropertySize, // '!siz'
-        BadSpecifierSize, // '!spc'
-        UnsupportedDataFormat = 1718449215, // 'fmt?'
-        UnknownFormat, // '!fmt'
-        Unknown, //
+        Unspecified           = UNSPECIFIED as isize,
+        UnsupportedProperty   = UNSUPPORTED_PROPERTY as isize,
+        BadPropertySize       = BAD_PROPERTY_SIZE as isize,
+        BadSpecifierSize      = BAD_SPECIFIER_SIZE as isize,
+        UnsupportedDataFormat = UNSUPPORTED_DATA_FORMAT as isize,
+        UnknownFormat         = UNKNOWN_FORMAT as isize,
+        Unknown,
     }
 
     impl Error {
 
         pub fn from_os_status(os_status: OSStatus) -> Result<(), Error> {
             match os_status {
-                0          => Ok(()),
-                1718449215 => Err(Error::UnsupportedDataFormat),
-                _ => Err(Error::Unknown),
+                0                       => Ok(()),
+                UNSPECIFIED             => Err(Error::Unspecified),
+                UNSUPPORTED_PROPERTY    => Err(Error::UnsupportedProperty),
+                BAD_PROPERTY_SIZE       => Err(Error::BadPropertySize),
+                BAD_SPECIFIER_SIZE      => Err(Error::BadSpecifierSize),
+                UNSUPPORTED_DATA_FORMAT => Err(Error::UnsupportedDataFormat),
+                UNKNOWN_FORMAT          => Err(Error::UnknownFormat),
+                _                       => Err(Error::Unknown),
             }
         }
 
         pub fn to_os_status(&self) -> OSStatus {
-            *self as OSStatus
+            match *self {
+                // `Unknown` has no status code of its own.
+                Error::Unknown => -1500,
+                err => err as OSStatus,
+            }
         }
 
     }
@@ -232,7 +269,11 @@ pub mod audio_unit {
         }
 
         pub fn to_os_status(&self) -> OSStatus {
-            *self as OSStatus
+            match *self {
+                // `Unknown` has no status code of its own.
+                Error::Unknown => -1500,
+                err => err as OSStatus,
+            }
         }
 
     }
@@ -278,12 +319,14 @@ pub enum Error {
     SystemSoundClientMessageTimedOut,
     NoMatchingDefaultAudioUnitFound,
     RenderCallbackBufferFormatDoesNotMatchAudioUnitStreamFormat,
+    RenderCallbackBufferSizeDoesNotMatchFrameCount,
+    PropertySizeDoesNotMatchType,
     NoKnownSubtype,
     Audio(AudioError),
     AudioCodec(AudioCodecError),
     AudioFormat(AudioFormatError),
     AudioUnit(AudioUnitError),
-    Unknown(OSStatus),
+    Unknown(FourCC),
 }
 
 impl Error {
@@ -315,7 +358,7 @@ impl Error {
                     Err(AudioUnitError::Unknown) => (),
                     Err(err)                     => return Err(Error::AudioUnit(err)),
                 }
-                Err(Error::Unknown(os_status))
+                Err(Error::Unknown(FourCC::from_os_status(os_status)))
             },
         }
     }
@@ -326,10 +369,14 @@ impl Error {
             Error::Unspecified                                                 => -1500,
             Error::NoMatchingDefaultAudioUnitFound                             => -1500,
             Error::RenderCallbackBufferFormatDoesNotMatchAudioUnitStreamFormat => -1500,
+            Error::RenderCallbackBufferSizeDoesNotMatchFrameCount              => -1500,
+            Error::PropertySizeDoesNotMatchType                                => -1500,
             Error::SystemSoundClientMessageTimedOut                            => -1501,
-            Error::Audio(err)                                                  => err as OSStatus,
-            Error::AudioCodec(err)                                             => err as OSStatus,
-            Error::AudioUnit(err)                                              => err as OSStatus,
+            Error::Audio(err)                                                  => err.to_os_status(),
+            Error::AudioCodec(err)                                             => err.to_os_status(),
+            Error::AudioFormat(err)                                            => err.to_os_status(),
+            Error::AudioUnit(err)                                              => err.to_os_status(),
+            Error::Unknown(code)                                               => code.to_os_status(),
             _                                                                  => -1500,
         }
     }
@@ -349,6 +396,10 @@ impl ::std::error::Error for Error {
             Error::NoMatchingDefaultAudioUnitFound  => "No matching default audio unit found",
             Error::RenderCallbackBufferFormatDoesNotMatchAudioUnitStreamFormat =>
                 "The given render callback buffer format does not match the `AudioUnit` `StreamFormat`",
+            Error::RenderCallbackBufferSizeDoesNotMatchFrameCount =>
+                "The size of the render callback buffer does not match the number of frames",
+            Error::PropertySizeDoesNotMatchType =>
+                "The size of the `AudioUnit` property does not match the size of the given type",
             Error::SystemSoundClientMessageTimedOut => "The system sound client message timed out",
             Error::NoKnownSubtype                   => "The type has no known subtypes",
             Error::Audio(ref err)                   => err.description(),
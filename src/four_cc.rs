@@ -0,0 +1,125 @@
+//! The four-character codes used throughout Core Audio to identify audio formats, audio unit types
+//! and error codes.
+//!
+//! A four-character code is a `u32` made up of four 8-bit characters, e.g. `'lpcm'` for the
+//! `LinearPCM` audio format or `'!siz'` for a bad property size error. Rendering these as their
+//! characters rather than as decimal integers makes them much easier to recognise in logs.
+
+use std::fmt;
+use std::str::FromStr;
+use sys::OSStatus;
+
+/// A four-character code.
+///
+/// Both the `Display` and `Debug` implementations render the code as its characters within single
+/// quotes, e.g. `'lpcm'`. Codes that contain non-printable characters (such as the negative
+/// `OSStatus` values used by the audio unit API) are rendered as a signed decimal integer instead.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FourCC(pub u32);
+
+/// The error returned when parsing a `FourCC` from a string that is not made up of exactly four
+/// bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseFourCCError;
+
+impl FourCC {
+
+    /// Construct a `FourCC` from its `u32` representation.
+    pub const fn new(code: u32) -> Self {
+        FourCC(code)
+    }
+
+    /// Construct a `FourCC` from its four characters, e.g. `FourCC::from_bytes(b"lpcm")`.
+    pub const fn from_bytes(bytes: &[u8; 4]) -> Self {
+        FourCC((bytes[0] as u32) << 24
+            | (bytes[1] as u32) << 16
+            | (bytes[2] as u32) << 8
+            | bytes[3] as u32)
+    }
+
+    /// Construct a `FourCC` from an `OSStatus` error code.
+    pub const fn from_os_status(os_status: OSStatus) -> Self {
+        FourCC(os_status as u32)
+    }
+
+    /// The `u32` representation of the code.
+    pub const fn to_u32(self) -> u32 {
+        self.0
+    }
+
+    /// The code as an `OSStatus`.
+    pub const fn to_os_status(self) -> OSStatus {
+        self.0 as OSStatus
+    }
+
+    /// The four characters that make up the code.
+    pub fn to_bytes(self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+
+    /// Whether or not all four characters of the code are printable ASCII.
+    pub fn is_printable(&self) -> bool {
+        self.to_bytes().iter().all(|&b| b >= 0x20 && b < 0x7f)
+    }
+
+}
+
+impl From<u32> for FourCC {
+    fn from(code: u32) -> Self {
+        FourCC(code)
+    }
+}
+
+impl From<FourCC> for u32 {
+    fn from(code: FourCC) -> Self {
+        code.0
+    }
+}
+
+impl fmt::Display for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_printable() {
+            let chars: String = self.to_bytes().iter().map(|&b| b as char).collect();
+            write!(f, "'{}'", chars)
+        } else {
+            write!(f, "{}", self.to_os_status())
+        }
+    }
+}
+
+impl fmt::Debug for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl FromStr for FourCC {
+    type Err = ParseFourCCError;
+    /// Parse a `FourCC` from four bytes, optionally surrounded by single quotes.
+    ///
+    /// E.g. both `lpcm` and `'lpcm'` produce the same `FourCC`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = if s.len() == 6 && s.starts_with('\'') && s.ends_with('\'') {
+            &s[1..5]
+        } else {
+            s
+        };
+        let bytes = s.as_bytes();
+        if bytes.len() != 4 {
+            return Err(ParseFourCCError);
+        }
+        Ok(FourCC::from_bytes(&[bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+impl fmt::Display for ParseFourCCError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ::std::error::Error for ParseFourCCError {
+    fn description(&self) -> &str {
+        "a four-character code must be made up of exactly four bytes"
+    }
+}
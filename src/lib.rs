@@ -12,7 +12,9 @@
 pub extern crate coreaudio_sys as sys;
 
 pub use error::Error;
+pub use four_cc::FourCC;
 
 #[cfg(feature = "audio_unit")]
 pub mod audio_unit;
 pub mod error;
+pub mod four_cc;
@@ -0,0 +1,69 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::audio_component::MANUFACTURER_APPLE;
+use coreaudio::audio_unit::{
+    AudioUnit,
+    ComponentDescription,
+    EffectType,
+    MidiProcessorType,
+    MusicEffectType,
+    Type,
+};
+use coreaudio::FourCC;
+
+#[test]
+fn wildcards_are_zero() {
+    let desc = ComponentDescription::any().to_sys();
+    assert_eq!(desc.componentType, 0);
+    assert_eq!(desc.componentSubType, 0);
+    assert_eq!(desc.componentManufacturer, 0);
+    assert_eq!(ComponentDescription::from_sys(desc), ComponentDescription::any());
+}
+
+#[test]
+fn description_round_trip() {
+    let desc = ComponentDescription::from_type(EffectType::Delay).manufacturer(MANUFACTURER_APPLE);
+    let sys = desc.to_sys();
+    assert_eq!(sys.componentType, FourCC::from_bytes(b"aufx").to_u32());
+    assert_eq!(sys.componentSubType, FourCC::from_bytes(b"dely").to_u32());
+    assert_eq!(sys.componentManufacturer, FourCC::from_bytes(b"appl").to_u32());
+    assert_eq!(ComponentDescription::from_sys(sys), desc);
+    assert_eq!(desc.ty(), Some(Type::Effect(EffectType::Delay)));
+}
+
+#[test]
+fn unknown_flag_bits_are_retained() {
+    let mut sys = ComponentDescription::any().to_sys();
+    sys.componentFlags = 0x8000_0002;
+    sys.componentFlagsMask = 0x4000_0000;
+    let desc = ComponentDescription::from_sys(sys);
+    assert_eq!(desc.flags.bits(), 0x8000_0002);
+    assert_eq!(desc.flags_mask.bits(), 0x4000_0000);
+    let sys = desc.to_sys();
+    assert_eq!(sys.componentFlags, 0x8000_0002);
+    assert_eq!(sys.componentFlagsMask, 0x4000_0000);
+}
+
+#[test]
+fn zero_subtypes_match_any_subtype() {
+    let desc = ComponentDescription::from_type(MidiProcessorType::Unknown(FourCC(0)));
+    assert_eq!(desc.component_type, Some(FourCC::from_bytes(b"aumi")));
+    assert_eq!(desc.component_subtype, None);
+    assert_eq!(desc.ty(), None);
+
+    let vendor = FourCC::from_bytes(b"Vndr");
+    let desc = ComponentDescription::from_type(MusicEffectType::Unknown(vendor));
+    assert_eq!(desc.component_type, Some(FourCC::from_bytes(b"aumf")));
+    assert_eq!(desc.component_subtype, Some(vendor));
+    assert_eq!(desc.ty(), Some(Type::MusicEffect(MusicEffectType::Unknown(vendor))));
+}
+
+#[test]
+fn music_effects_are_not_rejected_for_lacking_a_subtype() {
+    // Apple supplies no music effects, so none are found rather than the type being rejected.
+    match AudioUnit::new(MusicEffectType::Unknown(FourCC(0))) {
+        Err(coreaudio::Error::NoMatchingDefaultAudioUnitFound) => (),
+        Err(err) => panic!("unexpected {:?}", err),
+        Ok(_) => panic!("unexpected music effect"),
+    }
+}
@@ -0,0 +1,95 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::AudioFormat;
+use coreaudio::audio_unit::audio_format::{FlacFlags, LinearPcmFlags, Mpeg4ObjectId};
+use coreaudio::error::{AudioFormatError, AudioUnitError, Error};
+use coreaudio::FourCC;
+
+fn id(bytes: &[u8; 4]) -> u32 {
+    FourCC::from_bytes(bytes).to_u32()
+}
+
+#[test]
+fn formats_requiring_flags_fail_without_them() {
+    let formats = [b"lpcm", b"cac3", b"aac ", b"celp", b"hvxc", b"twvq", b"time", b"alac", b"flac"];
+    for bytes in &formats {
+        match AudioFormat::from_format_and_flag(id(bytes), None) {
+            Err(AudioFormatError::UnknownFormat) => (),
+            other => panic!("unexpected {:?} for {:?}", other, bytes),
+        }
+    }
+    match AudioFormat::from_format_and_flag(id(b"ac-3"), None) {
+        Ok(AudioFormat::AC3) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    match AudioFormat::from_format_and_flag(id(b"aac "), Some(2)) {
+        Ok(AudioFormat::MPEG4AAC(Mpeg4ObjectId::AAC_LC)) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_flag_bits_are_retained() {
+    let bits = (LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED).bits() | 0x4000_0000;
+    let format = AudioFormat::from_format_and_flag(id(b"lpcm"), Some(bits)).unwrap();
+    match format {
+        AudioFormat::LinearPCM(flags) => {
+            assert!(flags.contains(LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED));
+            assert_eq!(flags.bits(), bits);
+        },
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(format.to_format_and_flag(), (id(b"lpcm"), Some(bits)));
+}
+
+#[test]
+fn modern_format_ids() {
+    let formats = [
+        (AudioFormat::EnhancedAC3, b"ec-3", None),
+        (AudioFormat::FLAC(FlacFlags::BIT_24_SOURCE_DATA), b"flac", Some(3)),
+        (AudioFormat::Opus, b"opus", None),
+        (AudioFormat::LatmInLoas, b"loas", None),
+        (AudioFormat::MPEGD_USAC, b"usac", None),
+        (AudioFormat::APAC, b"apac", None),
+    ];
+    for &(format, bytes, flag) in &formats {
+        assert_eq!(format.format_id(), FourCC::from_bytes(bytes));
+        assert_eq!(format.to_format_and_flag(), (id(bytes), flag));
+        let round_trip = AudioFormat::from_format_and_flag(id(bytes), Some(flag.unwrap_or(0)));
+        let round_trip = round_trip.unwrap();
+        assert_eq!(format!("{:?}", round_trip), format!("{:?}", format));
+        assert_eq!(round_trip.to_format_and_flag(), (id(bytes), flag));
+    }
+}
+
+#[test]
+fn other_formats_round_trip() {
+    let format = AudioFormat::from_format_and_flag(id(b"abcd"), Some(3)).unwrap();
+    match format {
+        AudioFormat::Other(format, 3) => assert_eq!(format, id(b"abcd")),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(format.to_format_and_flag(), (id(b"abcd"), Some(3)));
+    assert_eq!(format.format_id(), FourCC::from_bytes(b"abcd"));
+    assert_eq!(format!("{}", format), "'abcd'");
+
+    let format = AudioFormat::from_format_and_flag(id(b"abcd"), None).unwrap();
+    assert_eq!(format.to_format_and_flag(), (id(b"abcd"), Some(0)));
+
+    // MPEG-4 formats with an unknown object ID.
+    let format = AudioFormat::from_format_and_flag(id(b"aac "), Some(99)).unwrap();
+    match format {
+        AudioFormat::Other(_, 99) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(format.to_format_and_flag(), (id(b"aac "), Some(99)));
+    assert_eq!(format!("{}", format), "'aac '");
+}
+
+#[test]
+fn unknown_errors_have_the_unspecified_status() {
+    assert_eq!(Error::AudioFormat(AudioFormatError::Unknown).to_os_status(), -1500);
+    assert_eq!(Error::AudioUnit(AudioUnitError::Unknown).to_os_status(), -1500);
+    let unknown_format = FourCC::from_bytes(b"!fmt").to_os_status();
+    assert_eq!(AudioFormatError::UnknownFormat.to_os_status(), unknown_format);
+}
@@ -0,0 +1,450 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::audio_format::LinearPcmFlags;
+use coreaudio::audio_unit::{AudioBufferListBuf, AudioUnit, EffectType, Element, IOType};
+use coreaudio::audio_unit::{FormatConverterType, Layout, MixerType, SampleFormat, Scope};
+use coreaudio::audio_unit::{StreamFormat, Type};
+use coreaudio::audio_unit::{self, property};
+use coreaudio::audio_unit::render_callback::{data, Args};
+use coreaudio::error::AudioUnitError;
+use coreaudio::sys;
+use coreaudio::{Error, FourCC};
+use std::ffi::CString;
+use std::{mem, ptr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn only_io_types_are_io() {
+    assert!(Type::from(IOType::GenericOutput).is_io());
+    assert!(!Type::from(EffectType::Delay).is_io());
+}
+
+#[test]
+fn close_does_not_stop_non_io_units() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    audio_unit.close().unwrap();
+    let audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    audio_unit.close().unwrap();
+}
+
+#[test]
+fn close_stops_io_units() {
+    let mut audio_unit = AudioUnit::new(IOType::GenericOutput).unwrap();
+    audio_unit.start().unwrap();
+    audio_unit.close().unwrap();
+}
+
+#[test]
+fn properties_required_before_initialization() {
+    let mut audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    audio_unit.set(property::MaximumFramesPerSlice, &512).unwrap();
+    let audio_unit = audio_unit.initialize().unwrap();
+    assert_eq!(audio_unit.get(property::MaximumFramesPerSlice).unwrap(), 512);
+}
+
+#[test]
+fn failed_initialization_returns_the_unit() {
+    let mut audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    let drops = Arc::new(AtomicUsize::new(0));
+    let counter = DropCounter(drops.clone());
+    audio_unit.set_render_callback(Element::Output, move |_: Args<data::Raw>| {
+        let _ = &counter;
+        Ok(())
+    }).unwrap();
+
+    // An effect's input and output channel counts must match.
+    let format = audio_unit.output_stream_format().unwrap();
+    let mono = StreamFormat { channels_per_frame: 1, ..format };
+    audio_unit.set_stream_format(mono, Scope::Input, Element::Output).unwrap();
+    let err = match audio_unit.initialize() {
+        Err(err) => err,
+        Ok(_) => panic!("unexpected Ok"),
+    };
+    match *err.error() {
+        Error::AudioUnit(AudioUnitError::FormatNotSupported) => (),
+        ref other => panic!("unexpected {:?}", other),
+    }
+
+    // The unit and its callback survive, so the format can be fixed and initialization retried.
+    let mut audio_unit = err.into_inner();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    audio_unit.set_stream_format(format, Scope::Input, Element::Output).unwrap();
+    let mut audio_unit = audio_unit.initialize().unwrap();
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    drop(audio_unit);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn property_vec_length_is_derived_from_the_size() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let id = sys::kAudioUnitProperty_ParameterList;
+    let info = audio_unit.get_property_info(id, Scope::Global, Element::Output).unwrap();
+    assert_eq!(info.size, 16);
+    assert!(!info.writable);
+
+    // Four `u32` parameter IDs.
+    let ids: Vec<u32> = audio_unit.get_property_vec(id, Scope::Global, Element::Output).unwrap();
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+    let pairs: Vec<u64> = audio_unit.get_property_vec(id, Scope::Global, Element::Output).unwrap();
+    assert_eq!(pairs.len(), 2);
+    let bytes = audio_unit.get_property_bytes(id, Scope::Global, Element::Output).unwrap();
+    assert_eq!(bytes.len(), 16);
+
+    // 16 bytes is not a multiple of the size of an ASBD.
+    let result = audio_unit.get_property_vec::<sys::AudioStreamBasicDescription>(
+        id,
+        Scope::Global,
+        Element::Output,
+    );
+    match result {
+        Err(Error::PropertySizeDoesNotMatchType) => (),
+        other => panic!("unexpected {:?}", other.map(|v| v.len())),
+    }
+}
+
+#[test]
+fn empty_property_vec() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let id = sys::kAudioUnitProperty_ParameterList;
+    assert_eq!(audio_unit.get_property_info(id, Scope::Input, Element::Output).unwrap().size, 0);
+    let ids: Vec<u32> = audio_unit.get_property_vec(id, Scope::Input, Element::Output).unwrap();
+    assert!(ids.is_empty());
+    let bytes = audio_unit.get_property_bytes(id, Scope::Input, Element::Output).unwrap();
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn property_size_must_match_the_type() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    // The element count is a `u32`.
+    let id = sys::kAudioUnitProperty_ElementCount;
+    match audio_unit.get_property::<f64>(id, Scope::Input, Element::Output) {
+        Err(Error::PropertySizeDoesNotMatchType) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    match audio_unit.get_property_cf_string(id, Scope::Input, Element::Output) {
+        Err(Error::PropertySizeDoesNotMatchType) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn cf_string_property() {
+    let mut audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let name = CString::new("Side chain").unwrap();
+    unsafe {
+        let encoding = sys::kCFStringEncodingUTF8;
+        let cf_name = sys::CFStringCreateWithCString(ptr::null(), name.as_ptr(), encoding);
+        let id = sys::kAudioUnitProperty_ElementName;
+        audio_unit.set_property(id, Scope::Input, Element::Output, Some(&cf_name)).unwrap();
+        sys::CFRelease(cf_name as sys::CFTypeRef);
+    }
+    let name = audio_unit.element_name(Scope::Input, Element::Output).unwrap();
+    assert_eq!(name, Some("Side chain".to_string()));
+}
+
+#[test]
+fn cf_dictionary_property() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let class_info = audio_unit.class_info().unwrap().unwrap();
+    assert!(!class_info.as_ptr().is_null());
+    let id = sys::kAudioUnitProperty_ClassInfo;
+    assert!(audio_unit.get_property_info(id, Scope::Global, Element::Output).unwrap().writable);
+}
+
+// Counts the number of times it has been dropped.
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn property_listener_is_called_on_its_scope_and_element() {
+    let mut audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    let changes = Arc::new(AtomicUsize::new(0));
+    let id = sys::kAudioUnitProperty_StreamFormat;
+    let listener = {
+        let changes = changes.clone();
+        audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+            changes.fetch_add(1, Ordering::SeqCst);
+        }).unwrap()
+    };
+
+    let asbd = audio_unit.get_scoped(property::StreamFormat, Scope::Input, Element::Output);
+    let asbd = asbd.unwrap();
+    audio_unit.set_scoped(property::StreamFormat, Scope::Input, Element::Output, &asbd).unwrap();
+    assert_eq!(changes.load(Ordering::SeqCst), 1);
+    audio_unit.set_scoped(property::StreamFormat, Scope::Output, Element::Output, &asbd).unwrap();
+    assert_eq!(changes.load(Ordering::SeqCst), 1);
+
+    drop(listener);
+    audio_unit.set_scoped(property::StreamFormat, Scope::Input, Element::Output, &asbd).unwrap();
+    assert_eq!(changes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn property_listener_closure_is_freed_on_drop() {
+    let mut audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let drops = Arc::new(AtomicUsize::new(0));
+    let id = sys::kAudioUnitProperty_StreamFormat;
+    let counter = DropCounter(drops.clone());
+    let listener = audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+        let _ = &counter;
+    }).unwrap();
+    drop(listener);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    // A guard that outlives its unit frees the closure once dropped.
+    let mut audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let counter = DropCounter(drops.clone());
+    let listener = audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+        let _ = &counter;
+    }).unwrap();
+    drop(audio_unit);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    drop(listener);
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn panicking_property_listener_does_not_unwind_into_the_unit() {
+    let mut audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let id = sys::kAudioUnitProperty_StreamFormat;
+    let _listener = {
+        let calls = calls.clone();
+        audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            panic!("property listener panic");
+        }).unwrap()
+    };
+    let asbd = audio_unit.get_scoped(property::StreamFormat, Scope::Input, Element::Output);
+    let asbd = asbd.unwrap();
+    audio_unit.set_scoped(property::StreamFormat, Scope::Input, Element::Output, &asbd).unwrap();
+    audio_unit.set_scoped(property::StreamFormat, Scope::Input, Element::Output, &asbd).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn property_listener_may_set_its_own_property() {
+    let mut audio_unit = AudioUnit::new_uninitialized(EffectType::Delay).unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let id = sys::kAudioUnitProperty_StreamFormat;
+    let asbd = audio_unit.get_scoped(property::StreamFormat, Scope::Input, Element::Output);
+    let asbd = asbd.unwrap();
+    // The raw instance is only used while the unit is alive.
+    let instance = *audio_unit.as_ref() as usize;
+    let _listener = {
+        let calls = calls.clone();
+        audio_unit.add_property_listener(id, Scope::Input, Element::Output, move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            // Setting the property calls the listener again, which is skipped.
+            let instance = instance as sys::AudioUnit;
+            let elem = Element::Output;
+            if audio_unit::set_property(instance, id, Scope::Input, elem, Some(&asbd)).is_ok() {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        }).unwrap()
+    };
+    audio_unit.set_scoped(property::StreamFormat, Scope::Input, Element::Output, &asbd).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn render_callbacks_are_kept_per_element() {
+    let mut audio_unit = AudioUnit::new(MixerType::MultiChannelMixer).unwrap();
+    let (calls0, calls1) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (drops0, drops1) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (calls, counter) = (calls0.clone(), DropCounter(drops0.clone()));
+    audio_unit.set_render_callback(Element(0), move |_: Args<data::Raw>| {
+        let _ = &counter;
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }).unwrap();
+    let (calls, counter) = (calls1.clone(), DropCounter(drops1.clone()));
+    audio_unit.set_render_callback(Element(1), move |_: Args<data::Raw>| {
+        let _ = &counter;
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }).unwrap();
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    assert_eq!(calls0.load(Ordering::SeqCst), 1);
+    assert_eq!(calls1.load(Ordering::SeqCst), 1);
+
+    // Replacing the callback of element 0 only uninstalls the old callback of element 0.
+    audio_unit.set_render_callback(Element(0), |_: Args<data::Raw>| Ok(())).unwrap();
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    assert_eq!(calls0.load(Ordering::SeqCst), 1);
+    assert_eq!(calls1.load(Ordering::SeqCst), 2);
+
+    // Removing the callback of element 1 uninstalls it.
+    assert!(audio_unit.free_render_callback(Element(1)).unwrap());
+    assert!(!audio_unit.free_render_callback(Element(1)).unwrap());
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    assert_eq!(calls1.load(Ordering::SeqCst), 2);
+    audio_unit.free_render_callbacks().unwrap();
+
+    // Core Audio may still be calling them, so they are only freed once the unit is disposed of.
+    assert_eq!(drops0.load(Ordering::SeqCst), 0);
+    assert_eq!(drops1.load(Ordering::SeqCst), 0);
+    drop(audio_unit);
+    assert_eq!(drops0.load(Ordering::SeqCst), 1);
+    assert_eq!(drops1.load(Ordering::SeqCst), 1);
+}
+
+// Renders `frames` frames of the unit's output, pulling its render callback.
+fn render(audio_unit: &mut AudioUnit, frames: u32) -> (sys::OSStatus, AudioBufferListBuf) {
+    let format = audio_unit.output_stream_format().unwrap();
+    let mut list = AudioBufferListBuf::from_stream_format(&format, frames as usize);
+    let mut flags: sys::AudioUnitRenderActionFlags = 0;
+    let mut time_stamp: sys::AudioTimeStamp = unsafe { mem::zeroed() };
+    time_stamp.mFlags = sys::kAudioTimeStampSampleTimeValid;
+    let status = unsafe {
+        let instance = *audio_unit.as_ref();
+        sys::AudioUnitRender(instance, &mut flags, &time_stamp, 0, frames, list.as_mut_ptr())
+    };
+    (status, list)
+}
+
+// The `f32` samples of each of the buffers.
+fn f32_samples(list: &AudioBufferListBuf) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for i in 0..list.num_buffers() {
+        for b in list.buffer(i).unwrap().chunks(4) {
+            let bits = b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24;
+            samples.push(f32::from_bits(bits));
+        }
+    }
+    samples
+}
+
+#[test]
+fn render_callback_errors_are_recorded_by_status() {
+    let mut audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    assert!(audio_unit.last_callback_error().is_none());
+
+    // An error with a status of its own is returned to Core Audio and recorded as is.
+    let code = FourCC::from_bytes(b"test");
+    audio_unit.set_render_callback(Element::Output, move |_: Args<data::Raw>| {
+        Err(Error::Unknown(code))
+    }).unwrap();
+    assert_eq!(render(&mut audio_unit, 64).0, code.to_os_status());
+    match audio_unit.last_callback_error() {
+        Some(Error::Unknown(c)) => assert_eq!(c, code),
+        other => panic!("unexpected {:?}", other),
+    }
+    match audio_unit.take_last_callback_error() {
+        Some(Error::Unknown(c)) => assert_eq!(c, code),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(audio_unit.last_callback_error().is_none());
+
+    // Errors specific to this crate share the unspecified status.
+    audio_unit.set_render_callback(Element::Output, |_: Args<data::Raw>| {
+        Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount)
+    }).unwrap();
+    assert_eq!(render(&mut audio_unit, 64).0, Error::Unspecified.to_os_status());
+    match audio_unit.take_last_callback_error() {
+        Some(Error::Unspecified) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(audio_unit.take_last_callback_error().is_none());
+}
+
+#[test]
+fn panicking_render_callback_is_poisoned_and_silenced() {
+    let mut audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    {
+        let calls = calls.clone();
+        audio_unit.set_render_callback(Element::Output, move |_: Args<data::Raw>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            panic!("render callback panic");
+        }).unwrap();
+    }
+    assert!(!audio_unit.has_callback_panicked());
+
+    // The panic does not unwind into Core Audio, which receives silence instead.
+    let (status, list) = render(&mut audio_unit, 64);
+    assert_eq!(status, 0);
+    assert!(f32_samples(&list).iter().all(|&s| s == 0.0));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(audio_unit.has_callback_panicked());
+
+    // The poisoned callback is never called again.
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let payload = audio_unit.take_callback_panic().unwrap();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"render callback panic"));
+    assert!(!audio_unit.has_callback_panicked());
+    assert!(audio_unit.take_callback_panic().is_none());
+
+    // Setting a new callback replaces the poisoned one.
+    {
+        let calls = calls.clone();
+        audio_unit.set_render_callback(Element::Output, move |_: Args<data::Raw>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }).unwrap();
+    }
+    assert_eq!(render(&mut audio_unit, 64).0, 0);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(!audio_unit.has_callback_panicked());
+}
+
+#[test]
+fn panicking_u8_render_callback_is_silenced_at_equilibrium() {
+    let mut audio_unit = AudioUnit::new_uninitialized(FormatConverterType::AUConverter).unwrap();
+    let format = StreamFormat {
+        sample_rate: audio_unit.output_stream_format().unwrap().sample_rate,
+        sample_format: SampleFormat::U8,
+        flags: LinearPcmFlags::IS_PACKED,
+        layout: Layout::Interleaved,
+        channels_per_frame: 2,
+        unpacked_bytes_per_sample: None,
+    };
+    audio_unit.set_stream_format(format, Scope::Input, Element::Output).unwrap();
+    let mut audio_unit = audio_unit.initialize().unwrap();
+    audio_unit.set_render_callback(Element::Output, |_: Args<data::Interleaved<u8>>| {
+        panic!("render callback panic");
+    }).unwrap();
+
+    // Zeroed `u8` samples would be converted to full scale negative samples.
+    let (status, list) = render(&mut audio_unit, 64);
+    assert_eq!(status, 0);
+    assert!(f32_samples(&list).iter().all(|&s| s == 0.0));
+    assert!(audio_unit.take_callback_panic().is_some());
+}
+
+#[test]
+fn input_callback_buffers_grow_with_maximum_frames_per_slice() {
+    let mut audio_unit = AudioUnit::new_uninitialized(IOType::HalOutput).unwrap();
+    audio_unit.set_input_callback(Element::Input, |_: Args<data::Raw>| Ok(())).unwrap();
+    // The buffers were allocated for the previous maximum when the callback was set.
+    audio_unit.set(property::MaximumFramesPerSlice, &4096).unwrap();
+    let audio_unit = match audio_unit.initialize() {
+        Ok(audio_unit) => audio_unit,
+        Err(err) => panic!("{}", err),
+    };
+
+    // Pull the input callback as the system would.
+    let id = sys::kAudioOutputUnitProperty_SetInputCallback;
+    let instance = *audio_unit.as_ref();
+    let callback: sys::AURenderCallbackStruct =
+        audio_unit::get_property(instance, id, Scope::Global, Element::Output).unwrap();
+    let mut flags: sys::AudioUnitRenderActionFlags = 0;
+    let mut time_stamp: sys::AudioTimeStamp = unsafe { mem::zeroed() };
+    time_stamp.mFlags = sys::kAudioTimeStampSampleTimeValid;
+    let status = unsafe {
+        let input_proc = callback.inputProc.unwrap();
+        input_proc(callback.inputProcRefCon, &mut flags, &time_stamp, 1, 4096, ptr::null_mut())
+    };
+    assert_eq!(status, 0);
+    assert!(audio_unit.last_callback_error().is_none());
+}
@@ -0,0 +1,42 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::audio_format::LinearPcmFlags;
+use coreaudio::audio_unit::{AudioFormat, EffectType, MidiProcessorType, Type};
+use coreaudio::error::{AudioFormatError, Error};
+use coreaudio::FourCC;
+
+#[test]
+fn display_and_parse() {
+    let lpcm = FourCC::from_bytes(b"lpcm");
+    assert_eq!(lpcm.to_u32(), 1819304813);
+    assert_eq!(format!("{}", lpcm), "'lpcm'");
+    assert_eq!(format!("{:?}", lpcm), "'lpcm'");
+    assert_eq!("lpcm".parse(), Ok(lpcm));
+    assert_eq!("'lpcm'".parse(), Ok(lpcm));
+    assert!("lpc".parse::<FourCC>().is_err());
+    assert_eq!(format!("{}", FourCC::from_os_status(-10879)), "-10879");
+}
+
+#[test]
+fn types_and_formats() {
+    let ty = Type::Effect(EffectType::PeakLimiter);
+    assert_eq!(format!("{}", ty), "'aufx'/'lmtr'");
+    assert_eq!(ty.to_u32(), 1635083896);
+    let ty = Type::MidiProcessor(MidiProcessorType::Unknown(FourCC::from_bytes(b"Vndr")));
+    assert_eq!(format!("{}", ty), "'aumi'/'Vndr'");
+    let format = AudioFormat::LinearPCM(LinearPcmFlags::IS_FLOAT);
+    assert_eq!(format.format_id(), FourCC::from_bytes(b"lpcm"));
+    assert_eq!(format!("{}", AudioFormat::Other(0x61626364, 3)), "'abcd'");
+}
+
+#[test]
+fn error_codes() {
+    match Error::from_os_status(FourCC::from_bytes(b"!fmt").to_os_status()) {
+        Err(Error::AudioFormat(AudioFormatError::UnknownFormat)) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    let unknown = FourCC::from_bytes(b"zzzz");
+    let err = Error::from_os_status(unknown.to_os_status()).unwrap_err();
+    assert_eq!(format!("{:?}", err), "Unknown('zzzz')");
+    assert_eq!(err.to_os_status(), unknown.to_os_status());
+}
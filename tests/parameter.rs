@@ -0,0 +1,141 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::{AudioUnit, EffectType, Element, Scope};
+use coreaudio::audio_unit::parameter::{
+    DelayParam, DynamicsProcessorParam, GraphicEQParam, NBandEQParam, Parameter, ParameterEvent,
+    ParameterEventKind, ParameterFlags, ParameterInfo, ParameterUnit,
+};
+use coreaudio::error::{AudioUnitError, Error};
+use coreaudio::sys;
+use std::mem;
+use std::os::raw::c_char;
+use std::thread;
+
+#[test]
+fn parameter_ids() {
+    assert_eq!(7u32.id(), 7);
+    assert_eq!(DelayParam::WetDryMix.id(), 0);
+    assert_eq!(DelayParam::LopassCutoff.id(), 3);
+    assert_eq!(DynamicsProcessorParam::OutputAmplitude.id(), 3000);
+    assert_eq!(GraphicEQParam::Band(30).id(), 30);
+    assert_eq!(GraphicEQParam::NumberOfBands.id(), 10000);
+    assert_eq!(NBandEQParam::GlobalGain.id(), 0);
+    assert_eq!(NBandEQParam::Gain(2).id(), 4002);
+    assert_eq!(NBandEQParam::Bandwidth(15).id(), 5015);
+}
+
+#[test]
+fn parameter_units() {
+    for u in 0..27 {
+        let unit = ParameterUnit::from_u32(u).unwrap();
+        assert_eq!(unit as u32, u);
+    }
+    assert_eq!(ParameterUnit::from_u32(27), None);
+}
+
+#[test]
+fn parameter_flags() {
+    let flags = ParameterFlags::from_bits_truncate(0xC000_0000);
+    assert!(flags.contains(ParameterFlags::IS_READABLE | ParameterFlags::IS_WRITABLE));
+    assert!(ParameterFlags::DISPLAY_MASK.contains(ParameterFlags::DISPLAY_LOGARITHMIC));
+}
+
+#[test]
+fn parameter_events() {
+    let event = ParameterEvent::immediate(DelayParam::WetDryMix, 64, 25.0);
+    assert_eq!(event.kind, ParameterEventKind::Immediate(25.0));
+    let sys_event = event.to_sys();
+    assert_eq!(sys_event.scope, Scope::Global as u32);
+    assert_eq!(sys_event.parameter, 0);
+    assert_eq!(sys_event.eventType, sys::kParameterEvent_Immediate);
+    unsafe {
+        assert_eq!(sys_event.eventValues.immediate.bufferOffset, 64);
+        assert_eq!(sys_event.eventValues.immediate.value, 25.0);
+    }
+
+    let event = ParameterEvent::ramp(NBandEQParam::Gain(1), 16, 128, -6.0, 6.0)
+        .scoped(Scope::Input, Element::Input);
+    let sys_event = event.to_sys();
+    assert_eq!(sys_event.scope, Scope::Input as u32);
+    assert_eq!(sys_event.element, Element::Input.to_u32());
+    assert_eq!(sys_event.parameter, 4001);
+    assert_eq!(sys_event.eventType, sys::kParameterEvent_Ramped);
+    unsafe {
+        let ramp = sys_event.eventValues.ramp;
+        assert_eq!(ramp.startBufferOffset, 16);
+        assert_eq!(ramp.durationInFrames, 128);
+        assert_eq!((ramp.startValue, ramp.endValue), (-6.0, 6.0));
+    }
+}
+
+fn sys_info(name: &[u8]) -> sys::AudioUnitParameterInfo {
+    let mut info: sys::AudioUnitParameterInfo = unsafe { mem::zeroed() };
+    for (c, &b) in info.name.iter_mut().zip(name) {
+        *c = b as c_char;
+    }
+    info.unit = ParameterUnit::Hertz as u32;
+    info.maxValue = 100.0;
+    info.flags = (ParameterFlags::IS_READABLE | ParameterFlags::IS_WRITABLE).bits();
+    info
+}
+
+#[test]
+fn parameter_info_names() {
+    let info = unsafe { ParameterInfo::from_sys(3, &sys_info(b"cutoff")) };
+    assert_eq!(info.id, 3);
+    assert_eq!(info.name, "cutoff");
+    assert_eq!(info.unit, Some(ParameterUnit::Hertz));
+    assert_eq!((info.min, info.max), (0.0, 100.0));
+    assert!(info.flags.contains(ParameterFlags::IS_READABLE | ParameterFlags::IS_WRITABLE));
+
+    // A name that fills the whole array has no nul terminator.
+    let name = [b'a'; 52];
+    let info = unsafe { ParameterInfo::from_sys(0, &sys_info(&name)) };
+    assert_eq!(info.name, "a".repeat(52));
+}
+
+#[test]
+fn parameter_discovery() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let params = audio_unit.parameters(Scope::Global).unwrap();
+    let ids: Vec<u32> = params.iter().map(|info| info.id).collect();
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+    assert!(params.iter().all(|info| !info.name.is_empty()));
+    assert!(audio_unit.parameters(Scope::Input).unwrap().is_empty());
+}
+
+#[test]
+fn scheduler_applies_events_in_chunks() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let scheduler = audio_unit.parameter_scheduler();
+    // More events than fit within a single chunk.
+    let events: Vec<ParameterEvent> = (0..40)
+        .map(|i| ParameterEvent::immediate(DelayParam::WetDryMix, 0, i as f32))
+        .collect();
+    scheduler.schedule(&events).unwrap();
+    let value = audio_unit.get_parameter(DelayParam::WetDryMix, Scope::Global, Element::Output);
+    assert_eq!(value.unwrap(), 39.0);
+
+    // Schedulers may be used concurrently.
+    let other = scheduler.clone();
+    let thread = thread::spawn(move || {
+        for _ in 0..100 {
+            other.schedule(&[ParameterEvent::immediate(DelayParam::Feedback, 0, 10.0)]).unwrap();
+        }
+    });
+    for _ in 0..100 {
+        scheduler.schedule(&[ParameterEvent::immediate(DelayParam::WetDryMix, 0, 20.0)]).unwrap();
+    }
+    thread.join().unwrap();
+}
+
+#[test]
+fn scheduler_fails_once_the_unit_is_disposed() {
+    let audio_unit = AudioUnit::new(EffectType::Delay).unwrap();
+    let scheduler = audio_unit.parameter_scheduler();
+    drop(audio_unit);
+    match scheduler.schedule(&[ParameterEvent::immediate(DelayParam::WetDryMix, 0, 1.0)]) {
+        Err(Error::AudioUnit(AudioUnitError::Uninitialized)) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+}
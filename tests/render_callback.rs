@@ -0,0 +1,209 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::{AudioBufferListBuf, SampleFormat, StreamFormat};
+use coreaudio::audio_unit::audio_buffer_list::ALIGN;
+use coreaudio::audio_unit::audio_format::LinearPcmFlags;
+use coreaudio::audio_unit::render_callback::action_flags::{ActionFlags, Handle};
+use coreaudio::audio_unit::render_callback::data::{Data, Interleaved, NonInterleaved};
+use coreaudio::audio_unit::stream_format::Layout;
+use coreaudio::sys;
+use coreaudio::{Error, FourCC};
+use std::os::raw::c_void;
+
+// An `AudioBufferList` with room for two buffers.
+#[repr(C)]
+#[allow(non_snake_case)]
+struct StereoBufferList {
+    mNumberBuffers: u32,
+    mBuffers: [sys::AudioBuffer; 2],
+}
+
+fn buffer_list(left: &mut [f32], right: &mut [f32]) -> StereoBufferList {
+    let buffer = |channel: &mut [f32]| sys::AudioBuffer {
+        mNumberChannels: 1,
+        mDataByteSize: (channel.len() * std::mem::size_of::<f32>()) as u32,
+        mData: channel.as_mut_ptr() as *mut c_void,
+    };
+    StereoBufferList {
+        mNumberBuffers: 2,
+        mBuffers: [buffer(left), buffer(right)],
+    }
+}
+
+fn non_interleaved(list: &mut StereoBufferList, frames: u32) -> NonInterleaved<f32> {
+    let ptr = list as *mut StereoBufferList as *mut sys::AudioBufferList;
+    unsafe { NonInterleaved::from_input_proc_args(frames, ptr).unwrap() }
+}
+
+#[test]
+fn frames_and_channels() {
+    let (mut left, mut right) = ([0.0f32; 4], [0.0f32; 4]);
+    let mut list = buffer_list(&mut left, &mut right);
+    {
+        let mut data = non_interleaved(&mut list, 4);
+        assert_eq!(data.num_channels(), 2);
+        assert_eq!(data.num_frames(), 4);
+        assert_eq!(data.frames_mut().len(), 4);
+        for (i, mut frame) in data.frames_mut().enumerate() {
+            assert_eq!(frame.len(), 2);
+            frame.fill(i as f32);
+            *frame.get_mut(1).unwrap() += 0.5;
+            assert!(frame.get(2).is_none());
+        }
+        assert_eq!(data.channel(0), Some(&[0.0, 1.0, 2.0, 3.0][..]));
+        data.channel_mut(1).unwrap()[0] = -1.0;
+        assert!(data.channel(2).is_none());
+    }
+    assert_eq!(left, [0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(right, [-1.0, 1.5, 2.5, 3.5]);
+}
+
+#[test]
+fn copy_fill_and_silence() {
+    let (mut src_left, mut src_right) = ([1.0f32, 2.0, 3.0], [4.0f32, 5.0, 6.0]);
+    let (mut dst_left, mut dst_right) = ([0.0f32; 3], [0.0f32; 3]);
+    let mut src_list = buffer_list(&mut src_left, &mut src_right);
+    let mut dst_list = buffer_list(&mut dst_left, &mut dst_right);
+    let src = non_interleaved(&mut src_list, 3);
+    let mut dst = non_interleaved(&mut dst_list, 3);
+
+    dst.copy_from(&src);
+    assert_eq!(dst.channel(0), src.channel(0));
+    assert_eq!(dst.channel(1), src.channel(1));
+
+    for (dst, src) in dst.zip_channels_mut(&src) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = s * 2.0;
+        }
+    }
+    assert_eq!(dst.channel(1), Some(&[8.0, 10.0, 12.0][..]));
+
+    dst.fill(0.25);
+    assert!(dst.channels().all(|channel| channel.iter().all(|&s| s == 0.25)));
+
+    let mut raw_flags: sys::AudioUnitRenderActionFlags = 0;
+    let mut flags = Handle::from_ptr(&mut raw_flags);
+    dst.silence(&mut flags);
+    assert!(dst.channels().all(|channel| channel.iter().all(|&s| s == 0.0)));
+    assert!(flags.contains(ActionFlags::OUTPUT_IS_SILENCE));
+}
+
+// `AudioBufferListBuf`.
+
+#[test]
+fn buffer_list_layout() {
+    let mut list = AudioBufferListBuf::new(3, 1, 10);
+    assert_eq!(list.num_buffers(), 3);
+    assert_eq!(list.capacity(), 16);
+    unsafe {
+        assert_eq!((*list.as_ptr()).mNumberBuffers, 3);
+    }
+    for (i, buffer) in list.buffers().iter().enumerate() {
+        assert_eq!(buffer.mNumberChannels, 1);
+        assert_eq!(buffer.mDataByteSize, 10);
+        assert_eq!(buffer.mData as usize % ALIGN, 0);
+        assert_eq!(list.buffer(i).unwrap(), &[0; 10][..]);
+    }
+    assert!(list.buffer(3).is_none());
+
+    list.buffer_mut(2).unwrap()[9] = 7;
+    list.reserve(100);
+    assert_eq!(list.capacity(), 112);
+    assert_eq!(list.buffer(2).unwrap().len(), 100);
+    assert_eq!(list.buffer(2).unwrap()[9], 7);
+    for (i, buffer) in list.buffers().iter().enumerate() {
+        assert_eq!(buffer.mData as *const u8, list.buffer(i).unwrap().as_ptr());
+    }
+
+    // The buffers can still be written through the list after being reallocated.
+    unsafe {
+        let mut data = NonInterleaved::<u8>::from_input_proc_args(100, list.as_mut_ptr()).unwrap();
+        assert_eq!(data.channel(2).unwrap()[9], 7);
+        data.channel_mut(2).unwrap()[99] = 9;
+    }
+    assert_eq!(list.buffer(2).unwrap()[99], 9);
+
+    // The size is clamped to the capacity of the buffers.
+    list.set_data_byte_size(1000);
+    assert_eq!(list.buffers()[0].mDataByteSize, 112);
+    list.set_data_byte_size(8);
+    assert_eq!(list.buffers()[0].mDataByteSize, 8);
+    assert_eq!(list.buffer(0).unwrap().len(), 8);
+}
+
+#[test]
+fn buffer_list_from_stream_format() {
+    let mut format = StreamFormat {
+        sample_rate: 44_100.0,
+        sample_format: SampleFormat::F32,
+        flags: LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED,
+        layout: Layout::NonInterleaved,
+        channels_per_frame: 2,
+        unpacked_bytes_per_sample: None,
+    };
+    let list = AudioBufferListBuf::from_stream_format(&format, 64);
+    assert_eq!(list.num_buffers(), 2);
+    assert!(list.buffers().iter().all(|b| b.mNumberChannels == 1 && b.mDataByteSize == 256));
+
+    format.layout = Layout::Interleaved;
+    let list = AudioBufferListBuf::from_stream_format(&format, 64);
+    assert_eq!(list.num_buffers(), 1);
+    assert_eq!(list.buffers()[0].mNumberChannels, 2);
+    assert_eq!(list.buffers()[0].mDataByteSize, 512);
+}
+
+// Validation of the callback's buffers.
+
+#[test]
+fn data_requires_matching_layout_flag() {
+    // Like the default stream format of `HalOutput`, which does not set `IS_NON_INTERLEAVED`.
+    let asbd = sys::AudioStreamBasicDescription {
+        mSampleRate: 44_100.0,
+        mFormatID: FourCC::from_bytes(b"lpcm").to_u32(),
+        mFormatFlags: (LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED).bits(),
+        mBytesPerPacket: 8,
+        mFramesPerPacket: 1,
+        mBytesPerFrame: 8,
+        mChannelsPerFrame: 2,
+        mBitsPerChannel: 32,
+        mReserved: 0,
+    };
+    let format = StreamFormat::from_asbd(asbd).unwrap();
+    assert_eq!(format.layout, Layout::Interleaved);
+    assert!(Interleaved::<f32>::does_stream_format_match(&format));
+    assert!(!NonInterleaved::<f32>::does_stream_format_match(&format));
+
+    let mut asbd = asbd;
+    asbd.mFormatFlags |= LinearPcmFlags::IS_NON_INTERLEAVED.bits();
+    asbd.mBytesPerPacket = 4;
+    asbd.mBytesPerFrame = 4;
+    let format = StreamFormat::from_asbd(asbd).unwrap();
+    assert_eq!(format.layout, Layout::NonInterleaved);
+    assert!(NonInterleaved::<f32>::does_stream_format_match(&format));
+    assert!(!Interleaved::<f32>::does_stream_format_match(&format));
+    assert!(!NonInterleaved::<i16>::does_stream_format_match(&format));
+}
+
+#[test]
+fn data_rejects_short_buffers() {
+    let mut list = AudioBufferListBuf::new(2, 1, 4 * 4);
+    unsafe {
+        assert!(NonInterleaved::<f32>::from_input_proc_args(4, list.as_mut_ptr()).is_ok());
+        assert!(NonInterleaved::<f32>::from_input_proc_args(5, list.as_mut_ptr()).is_err());
+        assert!(Interleaved::<f32>::from_input_proc_args(4, list.as_mut_ptr()).is_ok());
+        assert!(Interleaved::<f32>::from_input_proc_args(5, list.as_mut_ptr()).is_err());
+    }
+}
+
+#[test]
+fn non_interleaved_data_requires_one_channel_per_buffer() {
+    // Two buffers of two interleaved channels, large enough for 4 frames of every channel.
+    let mut list = AudioBufferListBuf::new(2, 2, 2 * 4 * 4);
+    unsafe {
+        match NonInterleaved::<f32>::from_input_proc_args(4, list.as_mut_ptr()) {
+            Err(Error::RenderCallbackBufferSizeDoesNotMatchFrameCount) => (),
+            Err(err) => panic!("unexpected {:?}", err),
+            Ok(_) => panic!("unexpected Ok"),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::{Fixed8_24, I24, Sample, SampleFormat, Tpdf};
+use coreaudio::audio_unit::sample_format::{
+    convert_bytes_to_slice, convert_slice, convert_slice_to_bytes,
+};
+
+#[test]
+fn signed_integer_scaling_and_clamping() {
+    assert_eq!(std::i16::MIN.to_f64(), -1.0);
+    assert_eq!(std::i16::MAX.to_f64(), 32767.0 / 32768.0);
+    assert_eq!(0i16.to_f64(), 0.0);
+    assert_eq!(i16::from_f64(-1.0), std::i16::MIN);
+    assert_eq!(i16::from_f64(1.0), std::i16::MAX);
+    assert_eq!(i16::from_f64(-1.5), std::i16::MIN);
+    assert_eq!(i16::from_f64(1.5), std::i16::MAX);
+    assert_eq!(i16::from_f64(0.5), 16384);
+    assert_eq!(i8::from_f64(1.0), std::i8::MAX);
+    assert_eq!(i32::from_f64(-1.0), std::i32::MIN);
+    assert_eq!(i32::from_f64(1.0), std::i32::MAX);
+    for &i in &[std::i16::MIN, -1, 0, 1, 12345, std::i16::MAX] {
+        assert_eq!(i16::from_f64(i.to_f64()), i);
+    }
+    for &i in &[std::i32::MIN, -1, 0, 1, 123456789, std::i32::MAX] {
+        assert_eq!(i32::from_f64(i.to_f64()), i);
+    }
+}
+
+#[test]
+fn unsigned_integer_offset() {
+    assert_eq!(u8::equilibrium(), 128);
+    assert_eq!(128u8.to_f64(), 0.0);
+    assert_eq!(0u8.to_f64(), -1.0);
+    assert_eq!(255u8.to_f64(), 127.0 / 128.0);
+    assert_eq!(u8::from_f64(0.0), 128);
+    assert_eq!(u8::from_f64(-1.0), 0);
+    assert_eq!(u8::from_f64(1.0), 255);
+    assert_eq!(u8::from_f64(-1.5), 0);
+    assert_eq!(u8::from_f64(1.5), 255);
+    for u in 0..256 {
+        assert_eq!(u8::from_f64((u as u8).to_f64()), u as u8);
+    }
+}
+
+#[test]
+fn i24_byte_order_and_sign_extension() {
+    let i = I24::from_i32(0x123456);
+    if cfg!(target_endian = "little") {
+        assert_eq!(i.0, [0x56, 0x34, 0x12]);
+    } else {
+        assert_eq!(i.0, [0x12, 0x34, 0x56]);
+    }
+    assert_eq!(i.to_i32(), 0x123456);
+
+    // 0x800000 is the most negative value.
+    let min = I24::from_i32(0x800000);
+    assert_eq!(min.to_i32(), I24::MIN);
+    assert_eq!(min.to_f64(), -1.0);
+    assert_eq!(I24::from_i32(0xFFFFFF).to_i32(), -1);
+    assert_eq!(I24::from_i32(I24::MAX).to_f64(), 8388607.0 / 8388608.0);
+    assert_eq!(I24::from_f64(-1.0).to_i32(), I24::MIN);
+    assert_eq!(I24::from_f64(1.0).to_i32(), I24::MAX);
+    assert_eq!(I24::from_f64(-2.0).to_i32(), I24::MIN);
+    assert_eq!(I24::from_f64(0.5).to_i32(), 0x400000);
+}
+
+#[test]
+fn fixed_point_conversion() {
+    assert_eq!(Fixed8_24(1 << 24).to_f64(), 1.0);
+    assert_eq!(Fixed8_24(-(1 << 24)).to_f64(), -1.0);
+    assert_eq!(Fixed8_24::from_f64(0.5), Fixed8_24(1 << 23));
+    // The top 8 bits provide headroom above full scale.
+    assert_eq!(Fixed8_24::from_f64(2.0), Fixed8_24(2 << 24));
+    assert_eq!(Fixed8_24::from_f64(1000.0), Fixed8_24(std::i32::MAX));
+    assert_eq!(Fixed8_24::from_f64(-1000.0), Fixed8_24(std::i32::MIN));
+}
+
+#[test]
+fn float_conversion() {
+    assert_eq!(f32::from_f64(0.25), 0.25);
+    assert_eq!(f32::to_sample::<i16>(1.0), std::i16::MAX);
+    assert_eq!(f64::to_sample::<i16>(-1.0), std::i16::MIN);
+    // Floating point formats are not clamped.
+    assert_eq!(f32::from_f64(1.5), 1.5);
+    assert_eq!(std::i16::MIN.to_sample::<f32>(), -1.0);
+}
+
+#[test]
+fn dither_bounds() {
+    let mut tpdf = Tpdf::new();
+    let mut sum = 0.0;
+    for _ in 0..10_000 {
+        let d = tpdf.next();
+        assert!(d > -1.0 && d < 1.0);
+        sum += d;
+    }
+    assert!((sum / 10_000.0).abs() < 0.05);
+
+    // Dithering moves each sample by at most one LSB of the target format.
+    let src: Vec<f32> = (0..1000).map(|i| (i as f32 / 1000.0) - 0.5).collect();
+    let (mut plain, mut dithered) = (vec![0i16; 1000], vec![0i16; 1000]);
+    convert_slice(&src, &mut plain, None);
+    convert_slice(&src, &mut dithered, Some(&mut Tpdf::with_seed(7)));
+    assert!(plain.iter().zip(&dithered).all(|(&a, &b)| (a as i32 - b as i32).abs() <= 1));
+    assert!(plain != dithered);
+
+    // No dither is applied when the bit depth does not decrease.
+    let src = [std::i16::MIN, -1, 0, 1, std::i16::MAX];
+    let mut dst = [0.0f32; 5];
+    convert_slice(&src, &mut dst, Some(&mut Tpdf::new()));
+    assert_eq!(dst, [-1.0, -1.0 / 32768.0, 0.0, 1.0 / 32768.0, 32767.0 / 32768.0]);
+}
+
+#[test]
+fn convert_slice_stops_at_shorter_slice() {
+    let mut dst = [0i16; 2];
+    convert_slice(&[1.0f32, -1.0, 0.5], &mut dst, None);
+    assert_eq!(dst, [std::i16::MAX, std::i16::MIN]);
+    let mut dst = [7u8; 3];
+    convert_slice(&[0.0f64], &mut dst, None);
+    assert_eq!(dst, [128, 7, 7]);
+}
+
+#[test]
+fn unaligned_byte_conversion_round_trip() {
+    let src = [0.5f32, -0.25, -1.0, 0.75];
+    let formats = [
+        SampleFormat::F32,
+        SampleFormat::F64,
+        SampleFormat::I32,
+        SampleFormat::I24,
+        SampleFormat::I16,
+        SampleFormat::U8,
+        SampleFormat::Fixed8_24,
+    ];
+    for &format in &formats {
+        // Offset the samples by a byte so that they are not aligned.
+        let size = format.size_in_bytes();
+        let mut bytes = vec![0u8; 1 + size * src.len()];
+        convert_slice_to_bytes(&src, format, &mut bytes[1..], None);
+        let mut dst = [0.0f32; 4];
+        convert_bytes_to_slice(format, &bytes[1..], &mut dst, None);
+        assert_eq!(dst, src, "{:?}", format);
+    }
+
+    let mut bytes = [0u8; 3];
+    convert_slice_to_bytes(&[std::i16::MIN], SampleFormat::I24, &mut bytes, None);
+    assert_eq!(I24(bytes).to_i32(), I24::MIN);
+}
+
+#[test]
+fn byte_conversion_stops_at_incomplete_samples() {
+    // Room for one and a half `i16`s.
+    let mut bytes = [0xAAu8; 3];
+    convert_slice_to_bytes(&[0.0f32, 0.0], SampleFormat::I16, &mut bytes, None);
+    assert_eq!(bytes, [0, 0, 0xAA]);
+
+    let mut dst = [1.0f32; 2];
+    convert_bytes_to_slice(SampleFormat::I16, &bytes, &mut dst, None);
+    assert_eq!(dst, [0.0, 1.0]);
+}
@@ -0,0 +1,120 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::audio_format::{LinearPcmFlags, Mpeg4ObjectId};
+use coreaudio::audio_unit::{AudioFormat, AudioStreamDescription, Layout, SampleFormat};
+use coreaudio::audio_unit::StreamFormat;
+use coreaudio::sys;
+use coreaudio::FourCC;
+
+fn pcm_asbd() -> sys::AudioStreamBasicDescription {
+    sys::AudioStreamBasicDescription {
+        mSampleRate: 48_000.0,
+        mFormatID: FourCC::from_bytes(b"lpcm").to_u32(),
+        mFormatFlags: (LinearPcmFlags::IS_SIGNED_INTEGER | LinearPcmFlags::IS_PACKED).bits(),
+        mBytesPerPacket: 4,
+        mFramesPerPacket: 1,
+        mBytesPerFrame: 4,
+        mChannelsPerFrame: 2,
+        mBitsPerChannel: 16,
+        mReserved: 0,
+    }
+}
+
+fn aac_asbd() -> sys::AudioStreamBasicDescription {
+    sys::AudioStreamBasicDescription {
+        mSampleRate: 44_100.0,
+        mFormatID: FourCC::from_bytes(b"aac ").to_u32(),
+        mFormatFlags: Mpeg4ObjectId::AAC_LC as u32,
+        mBytesPerPacket: 0,
+        mFramesPerPacket: 1024,
+        mBytesPerFrame: 0,
+        mChannelsPerFrame: 2,
+        mBitsPerChannel: 0,
+        mReserved: 0,
+    }
+}
+
+fn assert_asbd_eq(a: sys::AudioStreamBasicDescription, b: sys::AudioStreamBasicDescription) {
+    assert_eq!(a.mSampleRate, b.mSampleRate);
+    assert_eq!(a.mFormatID, b.mFormatID);
+    assert_eq!(a.mFormatFlags, b.mFormatFlags);
+    assert_eq!(a.mBytesPerPacket, b.mBytesPerPacket);
+    assert_eq!(a.mFramesPerPacket, b.mFramesPerPacket);
+    assert_eq!(a.mBytesPerFrame, b.mBytesPerFrame);
+    assert_eq!(a.mChannelsPerFrame, b.mChannelsPerFrame);
+    assert_eq!(a.mBitsPerChannel, b.mBitsPerChannel);
+}
+
+#[test]
+fn pcm_asbd_round_trip() {
+    let desc = AudioStreamDescription::from_asbd(pcm_asbd()).unwrap();
+    assert_eq!(desc.sample_rate, 48_000.0);
+    match desc.format {
+        AudioFormat::LinearPCM(flags) => assert!(flags.contains(LinearPcmFlags::IS_SIGNED_INTEGER)),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(desc.bytes_per_packet, Some(4));
+    assert!(!desc.is_variable_bytes_per_packet());
+    assert_eq!(desc.frames_per_packet, 1);
+    assert_eq!(desc.bytes_per_frame, 4);
+    assert_eq!(desc.channels_per_frame, 2);
+    assert_eq!(desc.bits_per_channel, 16);
+    assert!(desc.packet_descriptions.is_none());
+    assert!(desc.magic_cookie.is_none());
+    assert_asbd_eq(desc.to_asbd(), pcm_asbd());
+
+    let format = desc.try_into_stream_format().unwrap();
+    assert_eq!(format.sample_format, SampleFormat::I16);
+    assert_eq!(format.layout, Layout::Interleaved);
+    assert_eq!(format.channels_per_frame, 2);
+}
+
+#[test]
+fn aac_asbd_round_trip() {
+    let desc = AudioStreamDescription::from_asbd(aac_asbd()).unwrap();
+    match desc.format {
+        AudioFormat::MPEG4AAC(Mpeg4ObjectId::AAC_LC) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(desc.bytes_per_packet, None);
+    assert!(desc.is_variable_bytes_per_packet());
+    assert_eq!(desc.frames_per_packet, 1024);
+    assert_eq!(desc.bytes_per_frame, 0);
+    assert_eq!(desc.bits_per_channel, 0);
+    assert_asbd_eq(desc.to_asbd(), aac_asbd());
+
+    // AAC is not linear PCM, so it can't be used with the audio unit API.
+    assert!(desc.try_into_stream_format().is_err());
+}
+
+#[test]
+fn unknown_format_asbd_round_trip() {
+    // Formats unknown to `AudioFormat` are retained along with their flags.
+    let mut asbd = aac_asbd();
+    asbd.mFormatID = FourCC::from_bytes(b"abcd").to_u32();
+    let desc = AudioStreamDescription::from_asbd(asbd).unwrap();
+    assert_eq!(desc.format.format_id(), FourCC::from_bytes(b"abcd"));
+    assert_asbd_eq(desc.to_asbd(), asbd);
+}
+
+#[test]
+fn from_stream_format() {
+    let format = StreamFormat {
+        sample_rate: 44_100.0,
+        sample_format: SampleFormat::F32,
+        flags: LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED,
+        layout: Layout::NonInterleaved,
+        channels_per_frame: 2,
+        unpacked_bytes_per_sample: None,
+    };
+    let desc = AudioStreamDescription::from(format);
+    assert_eq!(desc.bytes_per_packet, Some(4));
+    assert_eq!(desc.bytes_per_frame, 4);
+    assert_eq!(desc.bits_per_channel, 32);
+    assert_asbd_eq(desc.to_asbd(), format.to_asbd());
+
+    let round_trip = desc.try_into_stream_format().unwrap();
+    assert_eq!(round_trip.sample_format, SampleFormat::F32);
+    assert_eq!(round_trip.layout, Layout::NonInterleaved);
+    assert_eq!(round_trip.flags, format.flags);
+}
@@ -0,0 +1,145 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::audio_format::LinearPcmFlags;
+use coreaudio::audio_unit::{I24, Layout, SampleFormat, StreamFormat};
+use coreaudio::audio_unit::render_callback::data::{Data, Interleaved};
+
+const SAMPLE_FORMATS: &'static [SampleFormat] = &[
+    SampleFormat::F32,
+    SampleFormat::F64,
+    SampleFormat::I32,
+    SampleFormat::I24,
+    SampleFormat::I16,
+    SampleFormat::I8,
+    SampleFormat::U8,
+    SampleFormat::Fixed8_24,
+];
+
+const LAYOUTS: &'static [Layout] = &[Layout::Interleaved, Layout::NonInterleaved];
+
+fn flags_for(sample_format: SampleFormat) -> LinearPcmFlags {
+    match sample_format {
+        SampleFormat::F32 |
+        SampleFormat::F64 => LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_PACKED,
+        SampleFormat::U8 => LinearPcmFlags::IS_PACKED,
+        SampleFormat::Fixed8_24 => (LinearPcmFlags::IS_SIGNED_INTEGER | LinearPcmFlags::IS_PACKED)
+            .with_sample_fraction_bits(24),
+        _ => LinearPcmFlags::IS_SIGNED_INTEGER | LinearPcmFlags::IS_PACKED,
+    }
+}
+
+#[test]
+fn asbd_round_trip() {
+    for &sample_format in SAMPLE_FORMATS {
+        for &layout in LAYOUTS {
+            for channels in 1..9 {
+                let format = StreamFormat {
+                    sample_rate: 44_100.0,
+                    sample_format: sample_format,
+                    flags: flags_for(sample_format),
+                    layout: layout,
+                    channels_per_frame: channels,
+                    unpacked_bytes_per_sample: None,
+                };
+                let asbd = format.to_asbd();
+
+                let sample_bytes = sample_format.size_in_bytes() as u32;
+                let bytes_per_frame = match layout {
+                    Layout::Interleaved => sample_bytes * channels,
+                    Layout::NonInterleaved => sample_bytes,
+                };
+                assert_eq!(asbd.mBytesPerFrame, bytes_per_frame);
+                assert_eq!(asbd.mBytesPerPacket, bytes_per_frame);
+                assert_eq!(asbd.mFramesPerPacket, 1);
+                assert_eq!(asbd.mBitsPerChannel, sample_bytes * 8);
+                assert_eq!(asbd.mChannelsPerFrame, channels);
+                let is_non_interleaved = asbd.mFormatFlags & LinearPcmFlags::IS_NON_INTERLEAVED.bits() != 0;
+                assert_eq!(is_non_interleaved, layout == Layout::NonInterleaved);
+
+                let round_trip = StreamFormat::from_asbd(asbd).unwrap();
+                assert_eq!(round_trip.sample_rate, format.sample_rate);
+                assert_eq!(round_trip.sample_format, format.sample_format);
+                assert_eq!(round_trip.flags, format.flags);
+                assert_eq!(round_trip.layout, format.layout);
+                assert_eq!(round_trip.channels_per_frame, format.channels_per_frame);
+            }
+        }
+    }
+}
+
+#[test]
+fn asbd_with_mismatched_bytes_per_frame_is_rejected() {
+    let format = StreamFormat {
+        sample_rate: 48_000.0,
+        sample_format: SampleFormat::F32,
+        flags: flags_for(SampleFormat::F32),
+        layout: Layout::Interleaved,
+        channels_per_frame: 2,
+        unpacked_bytes_per_sample: None,
+    };
+    let mut asbd = format.to_asbd();
+    asbd.mBytesPerFrame = 4;
+    assert!(StreamFormat::from_asbd(asbd).is_err());
+}
+
+#[test]
+fn unpacked_asbd_is_accepted() {
+    // 24-bit samples aligned high within 32 bits, as commonly reported by HAL devices.
+    let format = StreamFormat {
+        sample_rate: 48_000.0,
+        sample_format: SampleFormat::I24,
+        flags: LinearPcmFlags::IS_SIGNED_INTEGER | LinearPcmFlags::IS_ALIGNED_HIGH,
+        layout: Layout::Interleaved,
+        channels_per_frame: 2,
+        unpacked_bytes_per_sample: Some(4),
+    };
+    assert_eq!(format.bytes_per_sample(), 4);
+    let asbd = format.to_asbd();
+    assert_eq!(asbd.mBytesPerFrame, 8);
+    assert_eq!(asbd.mBytesPerPacket, 8);
+    assert_eq!(asbd.mBitsPerChannel, 24);
+    let round_trip = StreamFormat::from_asbd(asbd).unwrap();
+    assert_eq!(round_trip.sample_format, SampleFormat::I24);
+    assert_eq!(round_trip.unpacked_bytes_per_sample, Some(4));
+    assert_eq!(round_trip.bytes_per_frame(), 8);
+    assert!(!Interleaved::<I24>::does_stream_format_match(&round_trip));
+
+    // The same layout aligned low within each of the non-interleaved buffers.
+    let mut asbd = asbd;
+    asbd.mFormatFlags = (LinearPcmFlags::IS_SIGNED_INTEGER | LinearPcmFlags::IS_NON_INTERLEAVED)
+        .bits();
+    asbd.mBytesPerFrame = 4;
+    asbd.mBytesPerPacket = 4;
+    let round_trip = StreamFormat::from_asbd(asbd).unwrap();
+    assert_eq!(round_trip.layout, Layout::NonInterleaved);
+    assert_eq!(round_trip.unpacked_bytes_per_sample, Some(4));
+
+    // Packed samples must not be padded.
+    asbd.mFormatFlags |= LinearPcmFlags::IS_PACKED.bits();
+    assert!(StreamFormat::from_asbd(asbd).is_err());
+}
+
+#[test]
+fn sample_format_from_flags_and_bits_per_channel() {
+    for &sample_format in SAMPLE_FORMATS {
+        let flags = flags_for(sample_format);
+        let bits = sample_format.size_in_bytes() as u32 * 8;
+        assert!(sample_format.does_match_flags(flags));
+        assert_eq!(SampleFormat::from_flags_and_bits_per_channel(flags, bits), Some(sample_format));
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn sample_format_from_flags_and_bytes_per_frame() {
+    let flags = flags_for(SampleFormat::I16);
+    assert_eq!(SampleFormat::from_flags_and_bytes_per_frame(flags, 2), Some(SampleFormat::I16));
+    assert_eq!(SampleFormat::from_flags_and_bytes_per_frame(flags, 5), None);
+}
+
+#[test]
+fn i24_sign_extension() {
+    for &i in &[0, 1, -1, 1234567, -1234567, I24::MIN, I24::MAX] {
+        assert_eq!(I24::from_i32(i).to_i32(), i);
+    }
+}
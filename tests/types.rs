@@ -0,0 +1,176 @@
+extern crate coreaudio;
+
+use coreaudio::audio_unit::{
+    EffectType,
+    FormatConverterType,
+    GeneratorType,
+    IOType,
+    MidiProcessorType,
+    MixerType,
+    MusicDeviceType,
+    MusicEffectType,
+    OfflineEffectType,
+    PannerType,
+    Type,
+};
+use coreaudio::FourCC;
+
+const EFFECT_TYPES: &'static [EffectType] = &[
+    EffectType::PeakLimiter,
+    EffectType::DynamicsProcessor,
+    EffectType::LowPassFilter,
+    EffectType::HighPassFilter,
+    EffectType::BandPassFilter,
+    EffectType::HighShelfFilter,
+    EffectType::LowShelfFilter,
+    EffectType::ParametricEQ,
+    EffectType::Distortion,
+    EffectType::Delay,
+    EffectType::SampleDelay,
+    EffectType::GraphicEQ,
+    EffectType::MultiBandCompressor,
+    EffectType::MatrixReverb,
+    EffectType::Pitch,
+    EffectType::AUFilter,
+    EffectType::NetSend,
+    EffectType::RogerBeep,
+    EffectType::NBandEQ,
+];
+
+const FORMAT_CONVERTER_TYPES: &'static [FormatConverterType] = &[
+    FormatConverterType::AUConverter,
+    FormatConverterType::NewTimePitch,
+    FormatConverterType::TimePitch,
+    FormatConverterType::DeferredRenderer,
+    FormatConverterType::Splitter,
+    FormatConverterType::Merger,
+    FormatConverterType::Varispeed,
+    FormatConverterType::AUiPodTimeOther,
+    FormatConverterType::RoundTripAAC,
+];
+
+const MIXER_TYPES: &'static [MixerType] = &[
+    MixerType::MultiChannelMixer,
+    MixerType::StereoMixer,
+    MixerType::Mixer3D,
+    MixerType::MatrixMixer,
+    MixerType::SpatialMixer,
+];
+
+const GENERATOR_TYPES: &'static [GeneratorType] = &[
+    GeneratorType::ScheduledSoundPlayer,
+    GeneratorType::AudioFilePlayer,
+    GeneratorType::NetReceive,
+];
+
+const MUSIC_DEVICE_TYPES: &'static [MusicDeviceType] = &[
+    MusicDeviceType::DLSSynth,
+    MusicDeviceType::Sampler,
+    MusicDeviceType::MIDISynth,
+];
+
+const IO_TYPES: &'static [IOType] = &[
+    IOType::GenericOutput,
+    IOType::HalOutput,
+    IOType::DefaultOutput,
+    IOType::SystemOutput,
+    IOType::VoiceProcessingIO,
+    IOType::RemoteIO,
+];
+
+const PANNER_TYPES: &'static [PannerType] = &[
+    PannerType::SphericalHeadPanner,
+    PannerType::VectorPanner,
+    PannerType::SoundFieldPanner,
+    PannerType::HRTFPanner,
+];
+
+const OFFLINE_EFFECT_TYPES: &'static [OfflineEffectType] = &[
+    OfflineEffectType::TimePitch,
+    OfflineEffectType::NewTimePitch,
+    OfflineEffectType::Varispeed,
+];
+
+fn all_types() -> Vec<Type> {
+    let vendor = FourCC::from_bytes(b"Vndr");
+    let mut types = vec![
+        Type::MusicEffect(MusicEffectType::Unknown(vendor)),
+        Type::MidiProcessor(MidiProcessorType::Unknown(vendor)),
+    ];
+    types.extend(EFFECT_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(FORMAT_CONVERTER_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(MIXER_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(GENERATOR_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(MUSIC_DEVICE_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(IO_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(PANNER_TYPES.iter().map(|&ty| Type::from(ty)));
+    types.extend(OFFLINE_EFFECT_TYPES.iter().map(|&ty| Type::from(ty)));
+    types
+}
+
+#[test]
+fn subtype_round_trip() {
+    for &ty in EFFECT_TYPES {
+        assert_eq!(EffectType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in FORMAT_CONVERTER_TYPES {
+        assert_eq!(FormatConverterType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in MIXER_TYPES {
+        assert_eq!(MixerType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in GENERATOR_TYPES {
+        assert_eq!(GeneratorType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in MUSIC_DEVICE_TYPES {
+        assert_eq!(MusicDeviceType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in IO_TYPES {
+        assert_eq!(IOType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in PANNER_TYPES {
+        assert_eq!(PannerType::from_u32(ty as u32), Some(ty));
+    }
+    for &ty in OFFLINE_EFFECT_TYPES {
+        assert_eq!(OfflineEffectType::from_u32(ty as u32), Some(ty));
+    }
+}
+
+#[test]
+fn unknown_subtype() {
+    let code = FourCC::from_bytes(b"zzzz").to_u32();
+    assert_eq!(EffectType::from_u32(code), None);
+    assert_eq!(FormatConverterType::from_u32(code), None);
+    assert_eq!(MixerType::from_u32(code), None);
+    assert_eq!(GeneratorType::from_u32(code), None);
+    assert_eq!(MusicDeviceType::from_u32(code), None);
+    assert_eq!(IOType::from_u32(code), None);
+    assert_eq!(PannerType::from_u32(code), None);
+    assert_eq!(OfflineEffectType::from_u32(code), None);
+}
+
+#[test]
+fn type_round_trip() {
+    for ty in all_types() {
+        assert_eq!(Type::from_u32(ty.to_u32(), ty.to_subtype_u32().unwrap()), ty);
+    }
+}
+
+#[test]
+fn unknown_type_round_trip() {
+    let effect = FourCC::from_bytes(b"aufx");
+    let vendor = FourCC::from_bytes(b"Vndr");
+    let ty = Type::from_u32(effect.to_u32(), vendor.to_u32());
+    assert_eq!(ty, Type::Unknown(effect, vendor));
+    assert_eq!(ty.to_u32(), effect.to_u32());
+    assert_eq!(ty.to_subtype_u32(), Some(vendor.to_u32()));
+    assert_eq!(format!("{}", ty), "'aufx'/'Vndr'");
+
+    let ty = Type::from_u32(FourCC::from_bytes(b"aumi").to_u32(), vendor.to_u32());
+    assert_eq!(ty, Type::MidiProcessor(MidiProcessorType::Unknown(vendor)));
+    assert_eq!(format!("{}", ty), "'aumi'/'Vndr'");
+    let ty = Type::from_u32(FourCC::from_bytes(b"aumf").to_u32(), vendor.to_u32());
+    assert_eq!(ty, Type::MusicEffect(MusicEffectType::Unknown(vendor)));
+    assert_eq!(MusicEffectType::from_u32(vendor.to_u32()), Some(MusicEffectType::Unknown(vendor)));
+    assert_eq!(MusicEffectType::Unknown(vendor).to_u32(), vendor.to_u32());
+}